@@ -1,20 +1,29 @@
 extern crate proc_macro;
 extern crate syn;
-extern crate error_chain;
 
 extern crate prom_attire_impl;
 
-use error_chain::ChainedError;
+use prom_attire_impl::{Location, compile_errors};
 
 #[proc_macro_derive(PromAttireBootstrap, attributes(attire_bootstrap))]
 pub fn app(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input = &input.to_string();
+    let source = &input.to_string();
 
     let config = prom_attire_impl::Config {
+        krate: "prom_attire_impl",
         scope: Some("attire"),
+        scope_aliases: &[],
+        deprecated_scopes: &[],
         docs: None,
+        allow_unknown: false,
+        rest: None,
+        exclusive: &[],
+        at_least_one: &[],
+        validate: None,
+        rename_all: None,
+        bound: None,
         parse_field_config: &|attrs| {
-            attrs.first()
+            Ok(attrs.first()
                 .and_then(|attr| match attr.value {
                     syn::MetaItem::List(ref ident, ref values)
                         if ident == "attire_bootstrap" => values.first(),
@@ -35,27 +44,40 @@ pub fn app(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                             split_attribute_of: None,
                             default: prom_attire_impl::Defaulted::To("prom_attire_impl::Defaulted::Nope".to_owned()),
                             flag_value: Some("prom_attire_impl::Defaulted::Yep"),
+                            nested: false,
+                            capture: None,
+                            duplicate: prom_attire_impl::DuplicatePolicy::Error,
+                            required: false,
+                            separator: None,
+                            aliases: Vec::new(),
+                            deprecated_aliases: Vec::new(),
+                            deprecated: None,
+                            conflicts_with: Vec::new(),
+                            requires: Vec::new(),
+                            validate: None,
+                            min: None,
+                            max: None,
+                            min_len: None,
+                            max_len: None,
+                            non_empty: false,
                         }
                     }
                     _ => { return None; }
                 }))
-                .unwrap_or_else(|| prom_attire_impl::FieldConfig::default())
+                .unwrap_or_else(|| prom_attire_impl::FieldConfig::default()))
         }
     };
 
-    let expanded = match prom_attire_impl::derive(input, &config) {
+    let expanded = match prom_attire_impl::derive(source, &config) {
         Ok(expanded) => expanded,
-        Err(err) => {
-            println!("{}", err.display());
-            panic!("Expanding prom-attire-bootstrap failed");
-        }
+        Err(err) => return compile_errors(&input, &err.diagnostics()),
     };
 
     match expanded.parse() {
         Ok(parsed) => parsed,
         Err(err) => {
-            println!("{:?}", err);
-            panic!("Internal error in prom-attire-bootstrap");
+            let message = format!("Internal error in prom-attire-bootstrap: {:?}", err);
+            compile_errors(&input, &[(Location::Item, message)])
         }
     }
 }
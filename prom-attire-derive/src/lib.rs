@@ -0,0 +1,215 @@
+extern crate proc_macro;
+extern crate syn;
+
+#[macro_use]
+extern crate prom_attire_bootstrap;
+extern crate prom_attire_impl;
+
+use std::borrow::Cow;
+
+use prom_attire_impl::{FromAttributes, Location, compile_errors};
+
+#[derive(PromAttireBootstrap)]
+struct Attributes<'a> {
+    #[attire_bootstrap(scope)]
+    scope: Vec<&'a str>,
+    #[attire_bootstrap(docs)]
+    docs: Option<&'a str>,
+    #[attire_bootstrap(allow_unknown)]
+    allow_unknown: bool,
+    #[attire_bootstrap(deprecated_scope)]
+    deprecated_scope: Vec<&'a str>,
+    #[attire_bootstrap(rest)]
+    rest: Option<&'a str>,
+    #[attire_bootstrap(exclusive)]
+    exclusive: Vec<&'a str>,
+    #[attire_bootstrap(at_least_one)]
+    at_least_one: Vec<&'a str>,
+    #[attire_bootstrap(validate)]
+    validate: Option<&'a str>,
+    #[attire_bootstrap(rename_all)]
+    rename_all: Option<prom_attire_impl::RenameRule>,
+    #[attire_bootstrap(bound)]
+    bound: Option<&'a str>,
+}
+
+#[derive(PromAttireBootstrap)]
+struct FieldAttributes<'a> {
+    #[attire_bootstrap(field_attribute)]
+    attribute: Option<&'a str>,
+    #[attire_bootstrap(field_split_attribute_of)]
+    split_attribute_of: Option<&'a str>,
+    #[attire_bootstrap(field_default)]
+    default: prom_attire_impl::Defaulted,
+    #[attire_bootstrap(field_flag_value)]
+    flag_value: Option<&'a str>,
+    #[attire_bootstrap(field_nested)]
+    nested: bool,
+    #[attire_bootstrap(field_capture)]
+    capture: Option<prom_attire_impl::Capture>,
+    #[attire_bootstrap(field_duplicate)]
+    duplicate: Option<prom_attire_impl::DuplicatePolicy>,
+    #[attire_bootstrap(field_required)]
+    required: bool,
+    #[attire_bootstrap(field_separator)]
+    separator: Option<&'a str>,
+    #[attire_bootstrap(field_alias)]
+    alias: Vec<&'a str>,
+    #[attire_bootstrap(field_deprecated_alias)]
+    deprecated_alias: Vec<&'a str>,
+    #[attire_bootstrap(field_deprecated)]
+    deprecated: Option<&'a str>,
+    #[attire_bootstrap(field_conflicts_with)]
+    conflicts_with: Vec<&'a str>,
+    #[attire_bootstrap(field_requires)]
+    requires: Vec<&'a str>,
+    #[attire_bootstrap(field_validate)]
+    validate: Option<&'a str>,
+    #[attire_bootstrap(field_min)]
+    min: Option<Cow<'a, str>>,
+    #[attire_bootstrap(field_max)]
+    max: Option<Cow<'a, str>>,
+    #[attire_bootstrap(field_min_len)]
+    min_len: Option<usize>,
+    #[attire_bootstrap(field_max_len)]
+    max_len: Option<usize>,
+    #[attire_bootstrap(field_non_empty)]
+    non_empty: bool,
+}
+
+/// The procedural macro implementing `#[derive(PromAttire)]`
+#[proc_macro_derive(PromAttire, attributes(attire))]
+pub fn app(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let source = input.to_string();
+
+    let ast = match syn::parse_derive_input(&source) {
+        Ok(ast) => ast,
+        Err(err) => {
+            let message = format!("Internal error in prom-attire (probably): {}", err);
+            return compile_errors(&input, &[(Location::Item, message)]);
+        }
+    };
+
+    let attrs = match Attributes::try_from(ast.attrs.as_slice()) {
+        Ok(attrs) => attrs,
+        Err(errs) => {
+            let diagnostics = errs.iter()
+                .map(|err| (Location::Attribute("attire"), err.to_string()))
+                .collect::<Vec<_>>();
+            return compile_errors(&input, &diagnostics);
+        }
+    };
+
+    if attrs.scope.is_empty() && !attrs.deprecated_scope.is_empty() {
+        let message = "`deprecated_scope` can only be used along with a `scope`".to_owned();
+        return compile_errors(&input, &[(Location::Attribute("attire"), message)]);
+    }
+
+    let config = prom_attire_impl::Config {
+        krate: "prom_attire",
+        scope: attrs.scope.first().cloned(),
+        scope_aliases: attrs.scope.get(1..).unwrap_or(&[]),
+        deprecated_scopes: &attrs.deprecated_scope,
+        docs: attrs.docs,
+        allow_unknown: attrs.allow_unknown,
+        rest: attrs.rest,
+        exclusive: &attrs.exclusive,
+        at_least_one: &attrs.at_least_one,
+        validate: attrs.validate,
+        rename_all: attrs.rename_all,
+        bound: attrs.bound,
+        parse_field_config: &field_config,
+    };
+
+    let expanded = match prom_attire_impl::derive(&source, &config) {
+        Ok(expanded) => expanded,
+        Err(err) => return compile_errors(&input, &err.diagnostics()),
+    };
+
+    match expanded.parse() {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            let message = format!("Internal error in prom-attire: {:?}", err);
+            compile_errors(&input, &[(Location::Item, message)])
+        }
+    }
+}
+
+/// The procedural macro implementing `#[derive(AttireValue)]`
+#[proc_macro_derive(AttireValue, attributes(attire))]
+pub fn value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let source = input.to_string();
+
+    let ast = match syn::parse_derive_input(&source) {
+        Ok(ast) => ast,
+        Err(err) => {
+            let message = format!("Internal error in prom-attire (probably): {}", err);
+            return compile_errors(&input, &[(Location::Item, message)]);
+        }
+    };
+
+    if ast.attrs.iter().any(|attr| attr.name() == "attire") {
+        let message = "`AttireValue` enums take no `attire` attributes, only their variants do".to_owned();
+        return compile_errors(&input, &[(Location::Attribute("attire"), message)]);
+    }
+
+    let config = prom_attire_impl::Config {
+        krate: "prom_attire",
+        scope: None,
+        scope_aliases: &[],
+        deprecated_scopes: &[],
+        docs: None,
+        allow_unknown: false,
+        rest: None,
+        exclusive: &[],
+        at_least_one: &[],
+        validate: None,
+        rename_all: None,
+        bound: None,
+        parse_field_config: &field_config,
+    };
+
+    let expanded = match prom_attire_impl::derive_value(&source, &config) {
+        Ok(expanded) => expanded,
+        Err(err) => return compile_errors(&input, &err.diagnostics()),
+    };
+
+    match expanded.parse() {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            let message = format!("Internal error in prom-attire: {:?}", err);
+            compile_errors(&input, &[(Location::Item, message)])
+        }
+    }
+}
+
+fn field_config<'a>(attrs: &'a [syn::Attribute])
+    -> Result<prom_attire_impl::FieldConfig<'a>, Vec<String>> {
+    let attrs = FieldAttributes::try_from(attrs)
+        .map_err(|errs| {
+            errs.iter().map(|err| err.to_string()).collect::<Vec<_>>()
+        })?;
+    Ok(prom_attire_impl::FieldConfig {
+        attribute: attrs.attribute,
+        split_attribute_of: attrs.split_attribute_of,
+        default: attrs.default,
+        flag_value: attrs.flag_value,
+        nested: attrs.nested,
+        capture: attrs.capture,
+        duplicate: attrs.duplicate
+            .unwrap_or(prom_attire_impl::DuplicatePolicy::Error),
+        required: attrs.required,
+        separator: attrs.separator,
+        aliases: attrs.alias,
+        deprecated_aliases: attrs.deprecated_alias,
+        deprecated: attrs.deprecated,
+        conflicts_with: attrs.conflicts_with,
+        requires: attrs.requires,
+        validate: attrs.validate,
+        min: attrs.min,
+        max: attrs.max,
+        min_len: attrs.min_len,
+        max_len: attrs.max_len,
+        non_empty: attrs.non_empty,
+    })
+}
@@ -0,0 +1,207 @@
+use std::iter;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span,
+                 TokenStream, TokenTree};
+
+use errors::{Error, ErrorKind};
+
+/// The part of the derive input a diagnostic should be reported against
+#[derive(Debug, Eq, PartialEq)]
+pub enum Location<'a> {
+    /// The name of the item being derived on
+    Item,
+    /// An attribute on the item with the given identifier, e.g. `attire` for
+    /// `#[attire(scope = "shoes")]`
+    Attribute(&'a str),
+    /// A field or variant of the item, multiple elements are used to refer
+    /// to the fields of a variant
+    Member(Vec<&'a str>),
+}
+
+impl Error {
+    /// Converts this error into one or more diagnostics, each with the
+    /// location it should be reported against
+    pub fn diagnostics<'a>(&'a self) -> Vec<(Location<'a>, String)> {
+        let mut members = Vec::new();
+        let mut messages = Vec::new();
+        let mut current = Some(self);
+
+        while let Some(err) = current {
+            match *err.kind() {
+                ErrorKind::Variant(ref variant) => {
+                    members.push(variant.ident.as_ref());
+                }
+                ErrorKind::Field(ref field)
+                | ErrorKind::DocsTy(ref field)
+                | ErrorKind::RestTy(ref field)
+                | ErrorKind::SplitFieldTys(_, _, ref field) => {
+                    members.push(field.ident.as_ref().unwrap().as_ref());
+                }
+                ErrorKind::Attributes(ref errors) => {
+                    let location = || if members.is_empty() {
+                        Location::Item
+                    } else {
+                        Location::Member(members.clone())
+                    };
+                    return errors.iter()
+                        .map(|error| {
+                            let message = messages.iter()
+                                .chain(iter::once(error))
+                                .cloned()
+                                .collect::<Vec<_>>()
+                                .join(": ");
+                            (location(), message)
+                        })
+                        .collect();
+                }
+                _ => (),
+            }
+            messages.push(err.to_string());
+            current = err.1.next_error
+                .as_ref()
+                .and_then(|next| next.downcast_ref::<Error>());
+        }
+
+        let location = if members.is_empty() {
+            Location::Item
+        } else {
+            Location::Member(members)
+        };
+        vec![(location, messages.join(": "))]
+    }
+}
+
+/// Creates a `compile_error!` invocation for each diagnostic, spanned to
+/// the location in `input` the diagnostic refers to.
+///
+/// This must only be called from inside a procedural macro.
+pub fn compile_errors(input: &TokenStream, diagnostics: &[(Location, String)])
+    -> TokenStream {
+    diagnostics.iter()
+        .map(|diagnostic| {
+            let span = locate(input.clone(), &diagnostic.0)
+                .or_else(|| locate(input.clone(), &Location::Item))
+                .unwrap_or_else(Span::call_site);
+            compile_error(span, &diagnostic.1)
+        })
+        .collect()
+}
+
+fn compile_error(span: Span, message: &str) -> TokenStream {
+    let mut literal = Literal::string(message);
+    literal.set_span(span);
+    let mut args = Group::new(
+        Delimiter::Parenthesis,
+        TokenTree::Literal(literal).into());
+    args.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    let mut semi = Punct::new(';', Spacing::Alone);
+    semi.set_span(span);
+    vec![
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(args),
+        TokenTree::Punct(semi),
+    ].into_iter().collect()
+}
+
+fn locate(input: TokenStream, location: &Location) -> Option<Span> {
+    let tokens = input.into_iter().collect::<Vec<_>>();
+    match *location {
+        Location::Item => {
+            tokens.windows(2)
+                .filter_map(|pair| match pair[0] {
+                    TokenTree::Ident(ref keyword)
+                        if is_ident(keyword, "struct") || is_ident(keyword, "enum")
+                            => Some(pair[1].span()),
+                    _ => None,
+                })
+                .next()
+        }
+        Location::Attribute(name) => {
+            tokens.windows(2)
+                .filter_map(|pair| match pair[1] {
+                    TokenTree::Group(ref group)
+                        if is_punct(&pair[0], '#')
+                            && group.delimiter() == Delimiter::Bracket => {
+                        match group.stream().into_iter().next() {
+                            Some(TokenTree::Ident(ref ident))
+                                if is_ident(ident, name) => Some(group.span()),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+                .next()
+        }
+        Location::Member(ref path) => {
+            let mut body = body(&tokens);
+            let mut span = None;
+            for name in path {
+                let members = match body {
+                    Some(body) => body.stream().into_iter().collect::<Vec<_>>(),
+                    None => break,
+                };
+                let position = members.iter()
+                    .enumerate()
+                    .position(|(i, token)| match *token {
+                        TokenTree::Ident(ref ident) if is_ident(ident, name)
+                            => i == 0 || is_member_start(&members[i - 1]),
+                        _ => false,
+                    });
+                match position {
+                    Some(i) => {
+                        span = Some(members[i].span());
+                        body = body_of(&members[i + 1..]);
+                    }
+                    None => break,
+                }
+            }
+            span
+        }
+    }
+}
+
+fn is_ident(ident: &Ident, name: &str) -> bool {
+    ident.to_string() == name
+}
+
+fn is_punct(token: &TokenTree, c: char) -> bool {
+    match *token {
+        TokenTree::Punct(ref punct) => punct.as_char() == c,
+        _ => false,
+    }
+}
+
+/// Whether the token could be the one immediately preceding the identifier
+/// of a field or variant, i.e. a separating comma, the end of an attribute or
+/// a visibility qualifier
+fn is_member_start(token: &TokenTree) -> bool {
+    match *token {
+        TokenTree::Punct(ref punct) => punct.as_char() == ',',
+        TokenTree::Group(ref group) => group.delimiter() != Delimiter::Brace,
+        TokenTree::Ident(ref ident) => is_ident(ident, "pub"),
+        TokenTree::Literal(_) => false,
+    }
+}
+
+/// Finds the braced body of the item
+fn body(tokens: &[TokenTree]) -> Option<Group> {
+    tokens.iter()
+        .filter_map(|token| match *token {
+            TokenTree::Group(ref group)
+                if group.delimiter() == Delimiter::Brace => Some(group.clone()),
+            _ => None,
+        })
+        .next()
+}
+
+/// Finds the braced body of a struct variant, stopping at the end of the
+/// variant
+fn body_of(tokens: &[TokenTree]) -> Option<Group> {
+    let end = tokens.iter()
+        .position(|token| is_punct(token, ','))
+        .unwrap_or(tokens.len());
+    body(&tokens[..end])
+}
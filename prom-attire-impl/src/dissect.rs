@@ -1,27 +1,93 @@
-//! Takes an input struct and extracts all the details necessary to generate
-//! the From<&[Attribute]> implementation
+//! Takes an input struct or enum and extracts all the details necessary to
+//! generate the From<&[Attribute]> implementation
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
+use std::fmt::Debug;
+use std::str::FromStr;
 
 use syn;
 
 use errors::*;
 use tmp::{TryFrom, TryInto};
-use {Config, FieldConfig, Defaulted};
+use {Config, FieldConfig, Defaulted, Capture, DuplicatePolicy, RenameRule};
 
 #[derive(Debug)]
 pub struct Struct<'a> {
     pub ast: &'a syn::DeriveInput,
     pub docs: Option<Field<'a>>,
     pub lifetime: Option<&'a syn::Lifetime>,
+    /// Where predicates to use instead of inferring bounds for type
+    /// parameters
+    pub bound: Option<Vec<syn::WherePredicate>>,
     pub fields: Vec<Field<'a>>,
     pub split_fields: Vec<SplitFields<'a>>,
+    pub captures: Vec<CaptureField<'a>>,
+    pub rest: Option<RestField<'a>>,
+    pub constraints: Vec<Constraint<'a>>,
+    /// A function called with the finished struct
+    pub validate: Option<syn::Path>,
+}
+
+#[derive(Debug)]
+pub struct Enum<'a> {
+    pub ast: &'a syn::DeriveInput,
+    pub lifetime: Option<&'a syn::Lifetime>,
+    pub bound: Option<Vec<syn::WherePredicate>>,
+    pub variants: Vec<Variant<'a>>,
+    /// A function called with the finished enum
+    pub validate: Option<syn::Path>,
+}
+
+/// A fieldless enum parsed from a single word or string
+#[derive(Debug)]
+pub struct ValueEnum<'a> {
+    pub ast: &'a syn::DeriveInput,
+    pub variants: Vec<ValueVariant<'a>>,
+}
+
+#[derive(Debug)]
+pub struct ValueVariant<'a> {
+    pub ident: &'a syn::Ident,
+    /// The name listed as a valid choice
+    pub name: String,
+    /// Other names that also parse to this variant
+    pub aliases: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Variant<'a> {
+    pub ident: &'a syn::Ident,
+    pub attribute: String,
+    pub kind: VariantKind<'a>,
+}
+
+#[derive(Debug)]
+pub enum VariantKind<'a> {
+    /// A unit variant, matched by a `MetaItem::Word`
+    Word,
+    /// A single field tuple variant, matched by a `MetaItem::NameValue`
+    NameValue(Ty<'a>),
+    /// A struct variant, matched by a `MetaItem::List` containing its fields
+    List(Vec<Field<'a>>, Vec<SplitFields<'a>>, Vec<Constraint<'a>>),
+}
+
+/// A rule about which fields may be specified together, checked once all the
+/// attributes have been parsed
+#[derive(Debug)]
+pub enum Constraint<'a> {
+    /// At most one of the fields may be specified
+    Exclusive(Vec<&'a syn::Ident>),
+    /// At least one of the fields must be specified
+    AtLeastOne(Vec<&'a syn::Ident>),
+    /// If the first field is specified the second must be too
+    Requires(&'a syn::Ident, &'a syn::Ident),
 }
 
 #[derive(Debug)]
 pub struct SplitFields<'a> {
-    pub parent: &'a str,
+    pub parent: String,
     pub ty: Wrapper<'a>,
     pub syn_ty: &'a syn::Ty,
     pub fields: Vec<Field<'a>>,
@@ -31,23 +97,100 @@ pub struct SplitFields<'a> {
 pub struct Field<'a> {
     pub ast: &'a syn::Field,
     pub ident: &'a syn::Ident,
-    pub attribute: &'a str,
+    pub attribute: String,
+    /// Other attribute names matched as if they were `attribute`
+    pub aliases: Vec<&'a str>,
+    /// Old attribute names matched as if they were `attribute` with a warning
+    pub deprecated_aliases: Vec<&'a str>,
+    /// The note for a warning when the field is used
+    pub deprecated: Option<&'a str>,
+    pub conflicts_with: Vec<&'a str>,
+    pub requires: Vec<&'a str>,
     pub default: Defaulted,
     pub flag_value: Option<&'a str>,
+    pub duplicate: DuplicatePolicy,
+    pub required: bool,
+    /// Splits a single string value into multiple elements
+    pub separator: Option<&'a str>,
+    /// A function called with each value parsed for the field
+    pub validate: Option<syn::Path>,
+    /// Bounds on each value, normalized to be written as literals of the
+    /// field's type
+    pub min: Option<String>,
+    pub max: Option<String>,
+    /// Bounds on the length of each string value, or of the whole collection
+    pub min_len: Option<usize>,
+    pub max_len: Option<usize>,
     pub ty: Wrapper<'a>,
 }
 
+/// A field storing a detail of the annotated item rather than an attribute
+#[derive(Debug)]
+pub struct CaptureField<'a> {
+    pub ast: &'a syn::Field,
+    pub ident: &'a syn::Ident,
+    pub capture: Capture,
+}
+
+/// A field collecting the attributes in scope that no other field matches
+#[derive(Debug)]
+pub struct RestField<'a> {
+    pub ident: &'a syn::Ident,
+    /// Whether the attributes are cloned rather than borrowed
+    pub owned: bool,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum Wrapper<'a> {
     None(Ty<'a>),
     Option(Ty<'a>),
     Vec(Ty<'a>),
+    HashSet(Ty<'a>),
+    BTreeSet(Ty<'a>),
+    /// A map from key to value, filled from the `key = value` items nested
+    /// in a list attribute
+    Map(MapKind, Ty<'a>, Ty<'a>),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MapKind {
+    Hash,
+    BTree,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum Ty<'a> {
     Literal(Lit),
     Custom(&'a syn::Ty),
+    /// Another attribute struct, parsed from the contents of a list
+    Nested(&'a syn::Ty),
+    /// A `syn` type, parsed from the contents of a string literal
+    Syn(SynTy, &'a syn::Ty),
+    /// A reference to part of the attributes, or a `Cow` borrowing from them
+    Borrowed(Borrowed, &'a syn::Ty),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Borrowed {
+    /// `&syn::Lit`, the literal value of the attribute
+    Lit,
+    /// `&syn::MetaItem`, the whole attribute whatever form it takes
+    MetaItem,
+    /// `&[syn::NestedMetaItem]`, the contents of a list attribute
+    NestedMetaItems,
+    /// `Cow<str>`, borrowing string literals and converting other literals
+    CowStr,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SynTy {
+    Path,
+    Ty,
+    Ident,
+    Expr,
+    WherePredicate,
+    /// The literal itself, whatever kind it is
+    Lit,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -70,19 +213,9 @@ impl<'a> TryFrom<(&'a syn::DeriveInput, &'a Config<'a>)> for Struct<'a> {
             _ => bail!(ErrorKind::StructBody),
         };
 
-        if !ast.generics.ty_params.is_empty() {
-            bail!(ErrorKind::TyParams(ast.generics.ty_params.clone()));
-        }
-
-        if ast.generics.lifetimes.len() > 1 {
-            bail!(ErrorKind::Lifetimes(ast.generics.lifetimes.clone()));
-        }
-
-        let lifetime = ast.generics
-            .lifetimes
-            .iter()
-            .next()
-            .map(|l| &l.lifetime);
+        let lifetime = lifetime(ast);
+        let bound = bound(config)?;
+        let validate = validate(config.validate)?;
 
         let docs_field = config.docs.and_then(|docs| {
             syn_fields.iter()
@@ -93,8 +226,8 @@ impl<'a> TryFrom<(&'a syn::DeriveInput, &'a Config<'a>)> for Struct<'a> {
         // Option<Result<T>> -> Result<Option<T>> for the ? op to apply to
         let docs = match docs_field {
             Some(field) => {
-                let config = (config.parse_field_config)(field.attrs
-                    .as_slice());
+                let config = field_config(config, field.attrs.as_slice())
+                    .chain_err(|| ErrorKind::Field(field.clone()))?;
                 let docs: Field = (field, config).try_into()?;
                 if docs.ty != Wrapper::Vec(Ty::Literal(Lit::Str)) {
                     bail!(ErrorKind::DocsTy(field.clone()));
@@ -104,62 +237,539 @@ impl<'a> TryFrom<(&'a syn::DeriveInput, &'a Config<'a>)> for Struct<'a> {
             None => None,
         };
 
-        let mut fields = Vec::with_capacity(syn_fields.len());
-        let mut split_fields = HashMap::new();
+        let rest = match config.rest {
+            Some(rest) => {
+                let field = syn_fields.iter()
+                    .find(|field| field.ident.as_ref().unwrap().as_ref() == rest)
+                    .ok_or_else(|| ErrorKind::RestMissing(rest.to_owned()))?;
+                Some(field.try_into()?)
+            }
+            None => None,
+        };
+
+        let skip = config.docs.iter().chain(&config.rest).cloned().collect::<Vec<_>>();
+        let (fields, split_fields, captures) =
+            fields(syn_fields, config, &skip)?;
+        let constraints = constraints(
+            &fields, &split_fields, config.exclusive, config.at_least_one)?;
+
+        Ok(Struct {
+            ast: ast,
+            docs: docs,
+            lifetime: lifetime,
+            bound: bound,
+            fields: fields,
+            split_fields: split_fields,
+            captures: captures,
+            rest: rest,
+            constraints: constraints,
+            validate: validate,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a syn::DeriveInput, &'a Config<'a>)> for Enum<'a> {
+    type Err = Error;
 
-        let parse_field_config = &config.parse_field_config;
+    fn try_from((ast, config): (&'a syn::DeriveInput, &'a Config<'a>))
+        -> Result<Self> {
+        let syn_variants = match ast.body {
+            syn::Body::Enum(ref variants) => variants,
+            _ => bail!(ErrorKind::EnumBody),
+        };
 
-        for syn_field in syn_fields {
-            if Some(syn_field.ident.as_ref().unwrap().as_ref()) ==
-               config.docs {
-                continue;
+        if syn_variants.is_empty() {
+            bail!(ErrorKind::EnumBody);
+        }
+
+        let lifetime = lifetime(ast);
+        let bound = bound(config)?;
+        let validate = validate(config.validate)?;
+
+        let variants = syn_variants.iter()
+            .map(|variant| (variant, config).try_into())
+            .collect::<Result<_>>()?;
+
+        Ok(Enum {
+            ast: ast,
+            lifetime: lifetime,
+            bound: bound,
+            variants: variants,
+            validate: validate,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a syn::DeriveInput, &'a Config<'a>)> for ValueEnum<'a> {
+    type Err = Error;
+
+    fn try_from((ast, config): (&'a syn::DeriveInput, &'a Config<'a>))
+        -> Result<Self> {
+        let syn_variants = match ast.body {
+            syn::Body::Enum(ref variants) if !variants.is_empty() => variants,
+            _ => bail!(ErrorKind::ValueBody),
+        };
+
+        let variants = syn_variants.iter()
+            .map(|variant| (variant, config).try_into())
+            .collect::<Result<Vec<ValueVariant>>>()?;
+
+        let mut names = Vec::new();
+        for variant in &variants {
+            for name in Some(&variant.name).into_iter().chain(&variant.aliases) {
+                if names.contains(&name) {
+                    bail!(ErrorKind::ValueName(name.clone()));
+                }
+                names.push(name);
             }
-            let field_config = parse_field_config(syn_field.attrs.as_slice());
-            match field_config.split_attribute_of {
-                None => fields.push((syn_field, field_config).try_into()?),
-                Some(parent) => {
-                    let field: Field = (syn_field, field_config).try_into()?;
-                    match split_fields.entry(parent) {
-                        Entry::Occupied(mut entry) => {
-                            let split: &mut SplitFields = entry.get_mut();
-                            if split.ty != field.ty {
-                                bail!(ErrorKind::SplitFieldTys(split.parent.to_owned(), split.syn_ty.clone(), syn_field.clone()));
-                            }
-                            split.fields.push(field);
-                        }
-                        Entry::Vacant(entry) => {
-                            entry.insert(SplitFields {
-                                parent: parent,
-                                ty: field.ty.clone(),
-                                syn_ty: &syn_field.ty,
-                                fields: vec![field],
-                            });
+        }
+
+        Ok(ValueEnum {
+            ast: ast,
+            variants: variants,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a syn::Variant, &'a Config<'a>)> for ValueVariant<'a> {
+    type Err = Error;
+
+    fn try_from((ast, config): (&'a syn::Variant, &'a Config<'a>))
+        -> Result<Self> {
+        let variant_config = field_config(config, ast.attrs.as_slice())
+            .and_then(|variant_config| variant_options(variant_config, &["attribute", "alias"]))
+            .chain_err(|| ErrorKind::Variant(ast.clone()))?;
+
+        if ast.data != syn::VariantData::Unit {
+            Err(Error::from_kind(ErrorKind::ValueVariantBody))
+                .chain_err(|| ErrorKind::Variant(ast.clone()))?;
+        }
+
+        let mut aliases = Vec::new();
+        let name = match variant_config.attribute {
+            Some(attribute) => attribute.to_owned(),
+            None => {
+                // Words can't contain `-` so the snake_case name is accepted
+                // too
+                let snake = snake_case(ast.ident.as_ref());
+                let kebab = RenameRule::KebabCase.apply(&snake);
+                if kebab != snake {
+                    aliases.push(snake);
+                }
+                kebab
+            }
+        };
+        aliases.extend(variant_config.aliases.iter().map(|&alias| alias.to_owned()));
+
+        Ok(ValueVariant {
+            ident: &ast.ident,
+            name: name,
+            aliases: aliases,
+        })
+    }
+}
+
+impl<'a> TryFrom<(&'a syn::Variant, &'a Config<'a>)> for Variant<'a> {
+    type Err = Error;
+
+    fn try_from((ast, config): (&'a syn::Variant, &'a Config<'a>))
+        -> Result<Self> {
+        let variant_config = field_config(config, ast.attrs.as_slice())
+            .and_then(|variant_config| variant_options(variant_config, &["attribute"]))
+            .chain_err(|| ErrorKind::Variant(ast.clone()))?;
+
+        let kind = match ast.data {
+            syn::VariantData::Unit => VariantKind::Word,
+            syn::VariantData::Tuple(ref fields) if fields.len() == 1 => {
+                let ty: Ty = (&fields[0].ty).try_into()
+                    .chain_err(|| ErrorKind::Variant(ast.clone()))?;
+                if ty.is_raw() {
+                    Err(Error::from_kind(ErrorKind::RawTy(fields[0].ty.clone())))
+                        .chain_err(|| ErrorKind::Variant(ast.clone()))?;
+                }
+                VariantKind::NameValue(ty)
+            }
+            syn::VariantData::Tuple(_) => {
+                Err(Error::from_kind(ErrorKind::VariantBody))
+                    .chain_err(|| ErrorKind::Variant(ast.clone()))?
+            }
+            syn::VariantData::Struct(ref syn_fields) => {
+                let (fields, split_fields, captures) =
+                    fields(syn_fields, config, &[])
+                        .chain_err(|| ErrorKind::Variant(ast.clone()))?;
+                if let Some(capture) = captures.first() {
+                    Err(Error::from_kind(ErrorKind::VariantCapture))
+                        .chain_err(|| ErrorKind::Field(capture.ast.clone()))
+                        .chain_err(|| ErrorKind::Variant(ast.clone()))?;
+                }
+                let constraints = constraints(&fields, &split_fields, &[], &[])
+                    .chain_err(|| ErrorKind::Variant(ast.clone()))?;
+                VariantKind::List(fields, split_fields, constraints)
+            }
+        };
+
+        Ok(Variant {
+            ident: &ast.ident,
+            attribute: variant_config.attribute
+                .map(|attribute| attribute.to_owned())
+                .unwrap_or_else(|| rename(config, snake_case(ast.ident.as_ref()))),
+            kind: kind,
+        })
+    }
+}
+
+/// The lifetime of the attributes borrowed from, any other lifetimes are
+/// carried through unchanged
+fn lifetime(ast: &syn::DeriveInput) -> Option<&syn::Lifetime> {
+    ast.generics
+        .lifetimes
+        .iter()
+        .next()
+        .map(|l| &l.lifetime)
+}
+
+fn bound(config: &Config) -> Result<Option<Vec<syn::WherePredicate>>> {
+    match config.bound {
+        Some(bound) => {
+            syn::parse_where_clause(&format!("where {}", bound))
+                .map(|clause| Some(clause.predicates))
+                .map_err(|err| ErrorKind::Bound(bound.to_owned(), err).into())
+        }
+        None => Ok(None),
+    }
+}
+
+fn validate(validate: Option<&str>) -> Result<Option<syn::Path>> {
+    match validate {
+        Some(path) => {
+            syn::parse_path(path)
+                .map(Some)
+                .map_err(|err| ErrorKind::Validate(path.to_owned(), err).into())
+        }
+        None => Ok(None),
+    }
+}
+
+fn fields<'a>(
+    syn_fields: &'a [syn::Field],
+    config: &'a Config<'a>,
+    skip: &[&str]
+) -> Result<(Vec<Field<'a>>, Vec<SplitFields<'a>>, Vec<CaptureField<'a>>)> {
+    let mut fields = Vec::with_capacity(syn_fields.len());
+    let mut split_fields = HashMap::new();
+    let mut captures = Vec::new();
+
+    for syn_field in syn_fields {
+        if skip.contains(&syn_field.ident.as_ref().unwrap().as_ref()) {
+            continue;
+        }
+        let field_config = field_config(config, syn_field.attrs.as_slice())
+            .chain_err(|| ErrorKind::Field(syn_field.clone()))?;
+        if let Some(capture) = field_config.capture {
+            captures.push((syn_field, capture).try_into()?);
+            continue;
+        }
+        let renamed = field_config.attribute.is_none();
+        let split_attribute_of = field_config.split_attribute_of;
+        let mut field: Field = (syn_field, field_config).try_into()?;
+        if renamed {
+            field.attribute = rename(config, field.attribute);
+        }
+        match split_attribute_of {
+            None => fields.push(field),
+            Some(parent) => {
+                match split_fields.entry(parent) {
+                    Entry::Occupied(mut entry) => {
+                        let split: &mut SplitFields = entry.get_mut();
+                        if split.ty != field.ty {
+                            bail!(ErrorKind::SplitFieldTys(split.parent.to_owned(), split.syn_ty.clone(), syn_field.clone()));
                         }
+                        split.fields.push(field);
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(SplitFields {
+                            parent: rename(config, parent.to_owned()),
+                            ty: field.ty.clone(),
+                            syn_ty: &syn_field.ty,
+                            fields: vec![field],
+                        });
                     }
                 }
             }
         }
+    }
 
-        Ok(Struct {
+    Ok((fields, split_fields.into_iter().map(|(_, v)| v).collect(), captures))
+}
+
+/// Converts an attribute name taken from a field or variant name to the
+/// `rename_all` case convention, if any
+fn rename(config: &Config, name: String) -> String {
+    match config.rename_all {
+        // syn can't parse attributes containing `-`, so the `snake_case` name
+        // is the only one that can be written
+        Some(RenameRule::KebabCase) => name,
+        Some(rule) => rule.apply(&name),
+        None => name,
+    }
+}
+
+/// Collects the constraints declared on `fields` along with the `exclusive`
+/// and `at_least_one` groups, checking that every field they name exists
+fn constraints<'a>(
+    fields: &[Field<'a>],
+    split_fields: &[SplitFields<'a>],
+    exclusive: &[&str],
+    at_least_one: &[&str]
+) -> Result<Vec<Constraint<'a>>> {
+    let all_fields = || {
+        fields.iter().chain(split_fields.iter().flat_map(|split| &split.fields))
+    };
+    let find = |name: &str| {
+        all_fields()
+            .find(|field| field.ident.as_ref() == name)
+            .map(|field| field.ident)
+            .ok_or_else(|| Error::from_kind(ErrorKind::ConstraintField(name.to_owned())))
+    };
+    let group = |names: &str| {
+        names.split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(&find)
+            .collect::<Result<Vec<_>>>()
+    };
+
+    let mut constraints = Vec::new();
+    for field in all_fields() {
+        for name in &field.conflicts_with {
+            let other = find(name).chain_err(|| ErrorKind::Field(field.ast.clone()))?;
+            // Both fields may declare the conflict, only check it once
+            let declared = constraints.iter().any(|constraint| match *constraint {
+                Constraint::Exclusive(ref idents) => {
+                    idents.len() == 2 && idents.contains(&field.ident) && idents.contains(&other)
+                }
+                Constraint::AtLeastOne(_) | Constraint::Requires(_, _) => false,
+            });
+            if !declared {
+                constraints.push(Constraint::Exclusive(vec![field.ident, other]));
+            }
+        }
+        for name in &field.requires {
+            let other = find(name).chain_err(|| ErrorKind::Field(field.ast.clone()))?;
+            constraints.push(Constraint::Requires(field.ident, other));
+        }
+    }
+    for names in exclusive {
+        constraints.push(Constraint::Exclusive(group(names)?));
+    }
+    for names in at_least_one {
+        constraints.push(Constraint::AtLeastOne(group(names)?));
+    }
+    Ok(constraints)
+}
+
+impl<'a> TryFrom<(&'a syn::Field, Capture)> for CaptureField<'a> {
+    type Err = Error;
+
+    fn try_from((ast, capture): (&'a syn::Field, Capture)) -> Result<Self> {
+        let is_option = match ast.ty {
+            syn::Ty::Path(None, ref path) => match path.segments.last() {
+                Some(segment) => segment.ident == "Option",
+                None => false,
+            },
+            _ => false,
+        };
+        if !is_option {
+            Err(Error::from_kind(ErrorKind::CaptureTy(ast.ty.clone())))
+                .chain_err(|| ErrorKind::Field(ast.clone()))?;
+        }
+        Ok(CaptureField {
             ast: ast,
-            docs: docs,
-            lifetime: lifetime,
-            fields: fields,
-            split_fields: split_fields.into_iter().map(|(_, v)| v).collect(),
+            ident: ast.ident.as_ref().unwrap(),
+            capture: capture,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a syn::Field> for RestField<'a> {
+    type Err = Error;
+
+    fn try_from(ast: &'a syn::Field) -> Result<Self> {
+        let elem = match ast.ty {
+            syn::Ty::Path(None, ref path) => match path.segments.last() {
+                Some(segment) if segment.ident == "Vec" => {
+                    ty_try_from_wrapped(&segment.parameters, &ast.ty, 1)
+                        .ok()
+                        .map(|tys| &tys[0])
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        let owned = match elem {
+            Some(ty) if is_meta_item(ty) => true,
+            Some(ty) if is_meta_item_ref(ty) => false,
+            _ => bail!(ErrorKind::RestTy(ast.clone())),
+        };
+        Ok(RestField {
+            ident: ast.ident.as_ref().unwrap(),
+            owned: owned,
         })
     }
 }
 
+fn field_config<'a>(config: &'a Config<'a>, attrs: &'a [syn::Attribute])
+    -> Result<FieldConfig<'a>> {
+    (config.parse_field_config)(attrs)
+        .map_err(|errors| ErrorKind::Attributes(errors).into())
+}
+
+/// Checks that a variant's config only sets the `allowed` options, the rest
+/// of the field options have no meaning for a variant
+fn variant_options<'a>(config: FieldConfig<'a>, allowed: &'static [&'static str])
+    -> Result<FieldConfig<'a>> {
+    let options = [
+        ("attribute", config.attribute.is_some()),
+        ("alias", !config.aliases.is_empty()),
+        ("split_attribute_of", config.split_attribute_of.is_some()),
+        ("default", config.default != Defaulted::Nope),
+        ("flag_value", config.flag_value.is_some()),
+        ("nested", config.nested),
+        ("capture", config.capture.is_some()),
+        ("duplicate", config.duplicate != DuplicatePolicy::Error),
+        ("required", config.required),
+        ("separator", config.separator.is_some()),
+        ("deprecated_alias", !config.deprecated_aliases.is_empty()),
+        ("deprecated", config.deprecated.is_some()),
+        ("conflicts_with", !config.conflicts_with.is_empty()),
+        ("requires", !config.requires.is_empty()),
+        ("validate", config.validate.is_some()),
+        ("min", config.min.is_some()),
+        ("max", config.max.is_some()),
+        ("min_len", config.min_len.is_some()),
+        ("max_len", config.max_len.is_some()),
+        ("non_empty", config.non_empty),
+    ];
+    let unsupported = options.iter()
+        .filter(|&&(name, set)| set && !allowed.contains(&name))
+        .map(|&(name, _)| name)
+        .collect::<Vec<_>>();
+    if unsupported.is_empty() {
+        Ok(config)
+    } else {
+        bail!(ErrorKind::VariantOptions(unsupported, allowed))
+    }
+}
+
+/// Parses a `min` or `max` bound as the literal type of the field, returning
+/// it formatted ready to be suffixed with the type and written as a literal
+fn range_bound(value: &str, lit: Lit) -> Option<String> {
+    fn int<T: FromStr + Debug>(value: &str) -> Option<String> {
+        value.trim().parse::<T>().ok().map(|value| format!("{:?}", value))
+    }
+    fn float<T: FromStr + Debug + Into<f64> + Copy>(value: &str) -> Option<String> {
+        value.trim()
+            .parse::<T>()
+            .ok()
+            .and_then(|value| if value.into().is_finite() {
+                Some(format!("{:?}", value))
+            } else {
+                None
+            })
+    }
+    match lit {
+        Lit::Int(syn::IntTy::U8) => int::<u8>(value),
+        Lit::Int(syn::IntTy::I8) => int::<i8>(value),
+        Lit::Int(syn::IntTy::U16) => int::<u16>(value),
+        Lit::Int(syn::IntTy::I16) => int::<i16>(value),
+        Lit::Int(syn::IntTy::U32) => int::<u32>(value),
+        Lit::Int(syn::IntTy::I32) => int::<i32>(value),
+        Lit::Int(syn::IntTy::U64) => int::<u64>(value),
+        Lit::Int(syn::IntTy::I64) => int::<i64>(value),
+        Lit::Int(syn::IntTy::Usize) => int::<usize>(value),
+        Lit::Int(syn::IntTy::Isize) => int::<isize>(value),
+        Lit::Float(syn::FloatTy::F32) => float::<f32>(value),
+        Lit::Float(syn::FloatTy::F64) => float::<f64>(value),
+        _ => None,
+    }
+}
+
+/// Converts a `CamelCase` variant name to the `snake_case` attribute name, a
+/// run of capitals is one word, e.g. `IOError` becomes `io_error`
+fn snake_case(ident: &str) -> String {
+    let chars = ident.chars().collect::<Vec<_>>();
+    let mut attribute = String::with_capacity(ident.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let follows_lower = i != 0
+                && (chars[i - 1].is_lowercase() || chars[i - 1].is_numeric());
+            let starts_word = i != 0
+                && chars[i - 1].is_uppercase()
+                && i + 1 < chars.len()
+                && chars[i + 1].is_lowercase();
+            if follows_lower || starts_word {
+                attribute.push('_');
+            }
+            attribute.extend(c.to_lowercase());
+        } else {
+            attribute.push(c);
+        }
+    }
+    attribute
+}
+
 impl<'a> TryFrom<(&'a syn::Field, FieldConfig<'a>)> for Field<'a> {
     type Err = Error;
 
     fn try_from((ast, config): (&'a syn::Field, FieldConfig<'a>))
         -> Result<Self> {
         let ident = ast.ident.as_ref().unwrap();
-        let ty = (&ast.ty).try_into()
+        let mut ty: Wrapper = (&ast.ty).try_into()
                 .chain_err(|| ErrorKind::Field(ast.clone()))?;
 
+        if config.nested {
+            ty = ty.nested(&ast.ty)
+                .chain_err(|| ErrorKind::Field(ast.clone()))?;
+        }
+
+        if config.required {
+            match (&ty, &config.default) {
+                (&Wrapper::None(_), &Defaulted::Nope) => (),
+                (&Wrapper::None(_), _)
+                    => Err(Error::from_kind(ErrorKind::RequiredDefault)).chain_err(|| ErrorKind::Field(ast.clone()))?,
+                _ => Err(Error::from_kind(ErrorKind::RequiredWrapper(ast.ty.clone()))).chain_err(|| ErrorKind::Field(ast.clone()))?,
+            }
+        }
+
+        if ty.inner().is_raw() {
+            let supported = match ty {
+                Wrapper::None(_) | Wrapper::Option(_) | Wrapper::Vec(_) => {
+                    config.flag_value.is_none() && config.split_attribute_of.is_none() && config.separator.is_none()
+                }
+                _ => false,
+            };
+            if !supported {
+                Err(Error::from_kind(ErrorKind::RawTy(ast.ty.clone()))).chain_err(|| ErrorKind::Field(ast.clone()))?;
+            }
+        }
+
+        if let Wrapper::Map(..) = ty {
+            if config.flag_value.is_some() || config.split_attribute_of.is_some() {
+                Err(Error::from_kind(ErrorKind::MapOptions)).chain_err(|| ErrorKind::Field(ast.clone()))?;
+            }
+        }
+
+        let separable = match *ty.inner() {
+            Ty::Nested(_) | Ty::Syn(SynTy::Lit, _) | Ty::Borrowed(Borrowed::Lit, _) => false,
+            _ => ty.is_collection(),
+        };
+        if config.separator.is_some() && !separable {
+            Err(Error::from_kind(ErrorKind::SeparatorTy(ast.ty.clone()))).chain_err(|| ErrorKind::Field(ast.clone()))?;
+        }
+
         let default = match (&ty, config.default) {
+            (&Wrapper::None(_), Defaulted::Nope) if config.required
+                => Defaulted::Nope,
             (&Wrapper::None(Ty::Literal(Lit::Bool)), Defaulted::Nope)
                 => Defaulted::Yep,
             (&Wrapper::None(_), Defaulted::Nope)
@@ -172,18 +782,78 @@ impl<'a> TryFrom<(&'a syn::Field, FieldConfig<'a>)> for Field<'a> {
             (&Wrapper::None(Ty::Literal(Lit::Bool)), _, None)
             | (&Wrapper::Option(Ty::Literal(Lit::Bool)), _, None)
                 => Some("true"),
-            (_, &Defaulted::Nope, Some(_))
+            (_, &Defaulted::Nope, Some(_)) if !config.required
                 => Err(Error::from_kind(ErrorKind::WordValueNoDefault)).chain_err(|| ErrorKind::Field(ast.clone()))?,
             (_, _, flag_value)
                 => flag_value,
         };
 
+        let validate = validate(config.validate)
+            .chain_err(|| ErrorKind::Field(ast.clone()))?;
+
+        let (min, max) = match (config.min, config.max) {
+            (None, None) => (None, None),
+            (min, max) => {
+                let lit = match *ty.inner() {
+                    Ty::Literal(lit @ Lit::Int(_)) | Ty::Literal(lit @ Lit::Float(_)) => lit,
+                    _ => Err(Error::from_kind(ErrorKind::RangeTy(ast.ty.clone()))).chain_err(|| ErrorKind::Field(ast.clone()))?,
+                };
+                let bound = |value: Option<Cow<str>>| -> Result<Option<String>> {
+                    match value {
+                        Some(value) => range_bound(&value, lit)
+                            .map(Some)
+                            .ok_or_else(|| ErrorKind::RangeValue(value.into_owned(), ast.ty.clone()).into()),
+                        None => Ok(None),
+                    }
+                };
+                (bound(min).chain_err(|| ErrorKind::Field(ast.clone()))?,
+                 bound(max).chain_err(|| ErrorKind::Field(ast.clone()))?)
+            }
+        };
+
+        let min_len = match (config.min_len, config.non_empty) {
+            (Some(min_len), _) => Some(min_len),
+            (None, true) => Some(1),
+            (None, false) => None,
+        };
+        let max_len = config.max_len;
+        if min_len.is_some() || max_len.is_some() {
+            let is_string = match *ty.inner() {
+                Ty::Literal(Lit::Str) | Ty::Borrowed(Borrowed::CowStr, _) => true,
+                Ty::Custom(ty) => *ty == syn::parse_type("String").unwrap(),
+                _ => false,
+            };
+            let supported = match ty {
+                Wrapper::None(_) | Wrapper::Option(_) => is_string,
+                Wrapper::Vec(_)
+                | Wrapper::HashSet(_)
+                | Wrapper::BTreeSet(_)
+                | Wrapper::Map(..) => true,
+            };
+            if !supported {
+                Err(Error::from_kind(ErrorKind::LenTy(ast.ty.clone()))).chain_err(|| ErrorKind::Field(ast.clone()))?;
+            }
+        }
+
         Ok(Field {
             ast: ast,
             ident: ident,
-            attribute: config.attribute.unwrap_or_else(|| ident.as_ref()),
+            attribute: config.attribute.unwrap_or_else(|| ident.as_ref()).to_owned(),
+            aliases: config.aliases,
+            deprecated_aliases: config.deprecated_aliases,
+            deprecated: config.deprecated,
+            conflicts_with: config.conflicts_with,
+            requires: config.requires,
             default: default,
             flag_value: flag_value,
+            duplicate: config.duplicate,
+            required: config.required,
+            separator: config.separator,
+            validate: validate,
+            min: min,
+            max: max,
+            min_len: min_len,
+            max_len: max_len,
             ty: ty,
         })
     }
@@ -196,13 +866,36 @@ impl<'a> TryFrom<&'a syn::Ty> for Wrapper<'a> {
         if let syn::Ty::Path(None, ref path) = *ty {
             if !path.global && path.segments.len() == 1 {
                 let segment = &path.segments[0];
-                if segment.ident.as_ref() == "Option" || segment.ident.as_ref() == "Vec" {
-                    let inner = ty_try_from_option_or_vec(&segment.parameters, ty)?;
-                    return match segment.ident.as_ref() {
-                        "Option" => Ok(Wrapper::Option(inner)),
-                        "Vec" => Ok(Wrapper::Vec(inner)),
-                        _ => unreachable!(),
+                let wrapper: Option<fn(Ty<'a>) -> Wrapper<'a>> =
+                    match segment.ident.as_ref() {
+                        "Option" => Some(Wrapper::Option),
+                        "Vec" => Some(Wrapper::Vec),
+                        "HashSet" => Some(Wrapper::HashSet),
+                        "BTreeSet" => Some(Wrapper::BTreeSet),
+                        _ => None,
                     };
+                if let Some(wrapper) = wrapper {
+                    let inner = ty_try_from_wrapped(&segment.parameters, ty, 1)?;
+                    return Ok(wrapper((&inner[0]).try_into()?));
+                }
+                let kind = match segment.ident.as_ref() {
+                    "HashMap" => Some(MapKind::Hash),
+                    "BTreeMap" => Some(MapKind::BTree),
+                    _ => None,
+                };
+                if let Some(kind) = kind {
+                    let inner = ty_try_from_wrapped(&segment.parameters, ty, 2)?;
+                    let key = (&inner[0]).try_into()?;
+                    match key {
+                        Ty::Syn(SynTy::Lit, _)
+                        | Ty::Borrowed(Borrowed::Lit, _)
+                        | Ty::Borrowed(Borrowed::MetaItem, _)
+                        | Ty::Borrowed(Borrowed::NestedMetaItems, _) => {
+                            bail!(ErrorKind::MapKeyTy(inner[0].clone()))
+                        }
+                        _ => (),
+                    }
+                    return Ok(Wrapper::Map(kind, key, (&inner[1]).try_into()?));
                 }
             }
         }
@@ -215,15 +908,45 @@ impl<'a> Wrapper<'a> {
         match *self {
             Wrapper::None(ref ty)
             | Wrapper::Option(ref ty)
-            | Wrapper::Vec(ref ty) => ty,
+            | Wrapper::Vec(ref ty)
+            | Wrapper::HashSet(ref ty)
+            | Wrapper::BTreeSet(ref ty)
+            | Wrapper::Map(_, _, ref ty) => ty,
         }
     }
+
+    /// Whether the field collects every value rather than keeping one
+    pub fn is_collection(&self) -> bool {
+        match *self {
+            Wrapper::None(_) | Wrapper::Option(_) | Wrapper::Map(..) => false,
+            Wrapper::Vec(_) | Wrapper::HashSet(_) | Wrapper::BTreeSet(_) => true,
+        }
+    }
+
+    fn nested(self, syn_ty: &syn::Ty) -> Result<Self> {
+        let nested = |ty| -> Result<Ty<'a>> {
+            match ty {
+                Ty::Custom(ty) => Ok(Ty::Nested(ty)),
+                _ => bail!(ErrorKind::NestedTy(syn_ty.clone())),
+            }
+        };
+        Ok(match self {
+            Wrapper::None(ty) => Wrapper::None(nested(ty)?),
+            Wrapper::Option(ty) => Wrapper::Option(nested(ty)?),
+            Wrapper::Vec(ty) => Wrapper::Vec(nested(ty)?),
+            Wrapper::HashSet(ty) => Wrapper::HashSet(nested(ty)?),
+            Wrapper::BTreeSet(ty) => Wrapper::BTreeSet(nested(ty)?),
+            Wrapper::Map(..) => bail!(ErrorKind::NestedTy(syn_ty.clone())),
+        })
+    }
 }
 
-fn ty_try_from_option_or_vec<'a>(
+/// The `count` type parameters of a wrapper type such as `Vec<T>`
+fn ty_try_from_wrapped<'a>(
     p: &'a syn::PathParameters,
-    ty: &'a syn::Ty
-) -> Result<Ty<'a>> {
+    ty: &'a syn::Ty,
+    count: usize
+) -> Result<&'a [syn::Ty]> {
     let data = if let syn::PathParameters::AngleBracketed(ref data) = *p {
         data
     } else {
@@ -234,11 +957,11 @@ fn ty_try_from_option_or_vec<'a>(
         bail!(ErrorKind::TyWrapperBad(ty.clone()));
     }
 
-    if data.types.len() != 1 {
+    if data.types.len() != count {
         bail!(ErrorKind::TyWrapperBad(ty.clone()));
     }
 
-    (&data.types[0]).try_into()
+    Ok(&data.types)
 }
 
 impl<'a> TryFrom<&'a syn::Ty> for Ty<'a> {
@@ -251,6 +974,13 @@ impl<'a> TryFrom<&'a syn::Ty> for Ty<'a> {
                     bail!(ErrorKind::Ty(ty.clone()));
                 }
 
+                if let Some(syn_ty) = syn_ty_try_from(path) {
+                    return Ok(Ty::Syn(syn_ty, ty));
+                }
+                if is_cow_str(path) {
+                    return Ok(Ty::Borrowed(Borrowed::CowStr, ty));
+                }
+
                 match path.segments[0].ident.as_ref() {
                     "bool" => Ty::Literal(Lit::Bool),
                     "char" => Ty::Literal(Lit::Char),
@@ -269,16 +999,30 @@ impl<'a> TryFrom<&'a syn::Ty> for Ty<'a> {
                     _ => Ty::Custom(ty),
                 }
             }
-            syn::Ty::Rptr(_, ref ty) => {
-                if ty.mutability != syn::Mutability::Immutable {
-                    bail!(ErrorKind::TyRef(ty.ty.clone()));
+            syn::Ty::Rptr(_, ref mut_ty) => {
+                if mut_ty.mutability != syn::Mutability::Immutable {
+                    bail!(ErrorKind::TyRef(mut_ty.ty.clone()));
                 }
-                if ty.ty == syn::parse_type("str").unwrap() {
-                    Ty::Literal(Lit::Str)
-                } else if ty.ty == syn::parse_type("[u8]").unwrap() {
-                    Ty::Literal(Lit::ByteStr)
-                } else {
-                    bail!(ErrorKind::TyRef(ty.ty.clone()))
+                match mut_ty.ty {
+                    syn::Ty::Path(None, ref path) if is_syn_path(path, "Lit") => {
+                        Ty::Borrowed(Borrowed::Lit, ty)
+                    }
+                    syn::Ty::Path(None, ref path) if is_syn_path(path, "MetaItem") => {
+                        Ty::Borrowed(Borrowed::MetaItem, ty)
+                    }
+                    syn::Ty::Slice(ref inner) => match **inner {
+                        syn::Ty::Path(None, ref path) if is_syn_path(path, "NestedMetaItem") => {
+                            Ty::Borrowed(Borrowed::NestedMetaItems, ty)
+                        }
+                        _ if mut_ty.ty == syn::parse_type("[u8]").unwrap() => {
+                            Ty::Literal(Lit::ByteStr)
+                        }
+                        _ => bail!(ErrorKind::TyRef(mut_ty.ty.clone())),
+                    },
+                    _ if mut_ty.ty == syn::parse_type("str").unwrap() => {
+                        Ty::Literal(Lit::Str)
+                    }
+                    _ => bail!(ErrorKind::TyRef(mut_ty.ty.clone())),
                 }
             }
             _ => bail!(ErrorKind::Ty(ty.clone())),
@@ -286,11 +1030,79 @@ impl<'a> TryFrom<&'a syn::Ty> for Ty<'a> {
     }
 }
 
+/// `syn` types are only recognised when written as `syn::Path` etc. so that
+/// a user's own `Path` type is still parsed via `FromStr`
+fn syn_ty_try_from(path: &syn::Path) -> Option<SynTy> {
+    if path.segments.len() != 2 || path.segments[0].ident.as_ref() != "syn" {
+        return None;
+    }
+    Some(match path.segments[1].ident.as_ref() {
+        "Path" => SynTy::Path,
+        "Ty" => SynTy::Ty,
+        "Ident" => SynTy::Ident,
+        "Expr" => SynTy::Expr,
+        "WherePredicate" => SynTy::WherePredicate,
+        "Lit" => SynTy::Lit,
+        _ => return None,
+    })
+}
+
+/// Whether `path` is `syn::<name>`
+fn is_syn_path(path: &syn::Path, name: &str) -> bool {
+    path.segments.len() == 2
+        && path.segments[0].ident.as_ref() == "syn"
+        && path.segments[1].ident.as_ref() == name
+}
+
+fn is_meta_item(ty: &syn::Ty) -> bool {
+    match *ty {
+        syn::Ty::Path(None, ref path) => is_syn_path(path, "MetaItem"),
+        _ => false,
+    }
+}
+
+fn is_meta_item_ref(ty: &syn::Ty) -> bool {
+    match *ty {
+        syn::Ty::Rptr(_, ref mut_ty) => {
+            mut_ty.mutability == syn::Mutability::Immutable
+                && is_meta_item(&mut_ty.ty)
+        }
+        _ => false,
+    }
+}
+
+/// Whether `path` is `Cow<'a, str>`, optionally qualified
+fn is_cow_str(path: &syn::Path) -> bool {
+    let segment = match path.segments.last() {
+        Some(segment) if segment.ident.as_ref() == "Cow" => segment,
+        _ => return false,
+    };
+    match segment.parameters {
+        syn::PathParameters::AngleBracketed(ref data) => {
+            data.lifetimes.len() == 1
+                && data.bindings.is_empty()
+                && data.types == [syn::parse_type("str").unwrap()]
+        }
+        syn::PathParameters::Parenthesized(_) => false,
+    }
+}
+
 impl<'a> Ty<'a> {
     pub fn lit(&self) -> Option<Lit> {
         match *self {
             Ty::Literal(lit) => Some(lit),
-            Ty::Custom(_) => None,
+            Ty::Custom(_) | Ty::Nested(_) | Ty::Syn(_, _) | Ty::Borrowed(_, _) => None,
+        }
+    }
+
+    /// Whether the value is a raw piece of the attribute rather than being
+    /// parsed from a literal
+    pub fn is_raw(&self) -> bool {
+        match *self {
+            Ty::Borrowed(borrowed, _) => {
+                borrowed == Borrowed::MetaItem || borrowed == Borrowed::NestedMetaItems
+            }
+            Ty::Literal(_) | Ty::Custom(_) | Ty::Nested(_) | Ty::Syn(_, _) => false,
         }
     }
 }
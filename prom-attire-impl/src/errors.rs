@@ -13,19 +13,65 @@ impl<T: quote::ToTokens> fmt::Display for Q<T> {
     }
 }
 
+/// Displays option names quoted and separated by the given string
+struct Names<'a>(&'a [&'a str], &'a str);
+impl<'a> fmt::Display for Names<'a> {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        for (i, name) in self.0.iter().enumerate() {
+            if i != 0 {
+                w.write_str(self.1)?;
+            }
+            write!(w, "`{}`", name)?;
+        }
+        Ok(())
+    }
+}
+
 error_chain! {
     errors {
         StructBody {
             description("can only be applied to normal structs")
         }
 
-        TyParams(ty_params: Vec<syn::TyParam>) {
-            description("cannot have type parameters on the struct")
+        EnumBody {
+            description("can only be applied to enums with at least 1 variant")
+        }
+
+        VariantBody {
+            description("variants must be unit, struct or have a single unnamed field")
+        }
+
+        ValueBody {
+            description("can only be applied to enums with at least 1 variant")
+        }
+
+        ValueVariantBody {
+            description("variants must be unit variants")
+        }
+
+        ValueName(name: String) {
+            description("value name used by multiple variants")
+            display("value `{}` is used by more than one variant", name)
         }
 
-        Lifetimes(lifetimes: Vec<syn::LifetimeDef>) {
-            description("can have a maximum of 1 lifetime on the struct")
-            display("can have a maximum of 1 lifetime on the struct, found `{}`", lifetimes.len())
+        VariantOptions(options: Vec<&'static str>, allowed: &'static [&'static str]) {
+            description("unsupported options on a variant")
+            display("{} cannot be used on a variant, only {}", Names(options, ", "), Names(allowed, " and "))
+        }
+
+        Variant(variant: syn::Variant) {
+            description("variant had an error")
+            display("variant `{}` had an error", variant.ident)
+        }
+
+        Bound(bound: String, err: String) {
+            description("invalid bound")
+            display("bound `{}` could not be parsed as where predicates: {}", bound, err)
+        }
+
+        Validate(path: String, err: String) {
+            description("invalid validate function")
+            display("validate function `{}` could not be parsed as a path: {}", path, err)
         }
 
         SplitFieldTys(split: String, ty: syn::Ty, field: syn::Field) {
@@ -47,6 +93,20 @@ error_chain! {
             description("if a `flag_value` is specified a `default` must also be specified")
         }
 
+        RequiredDefault {
+            description("a field cannot be both `required` and have a `default`")
+        }
+
+        RequiredWrapper(ty: syn::Ty) {
+            description("required fields cannot be wrapped")
+            display("required field type `{}` must not be enclosed in a `Vec` or `Option`", Q(&ty))
+        }
+
+        SeparatorTy(ty: syn::Ty) {
+            description("only collections can have a separator")
+            display("field type `{}` with a `separator` must be a `Vec`, `HashSet` or `BTreeSet` of non-nested values other than `syn::Lit`", Q(&ty))
+        }
+
         Ty(ty: syn::Ty) {
             description("unsupported type")
             display("type `{}` is not supported", Q(&ty))
@@ -58,18 +118,81 @@ error_chain! {
         }
 
         TyWrapperBad(ty: syn::Ty) {
-            description("invalid Option/Vec type wrapper")
-            display("type `{}` is not supported, it appears to be a `Vec` or `Option` but could not be parsed", Q(&ty))
+            description("invalid Option/Vec/set/map type wrapper")
+            display("type `{}` is not supported, it appears to be a `Vec`, `HashSet`, `BTreeSet`, `HashMap`, `BTreeMap` or `Option` but could not be parsed", Q(&ty))
+        }
+
+        RawTy(ty: syn::Ty) {
+            description("unsupported use of a raw attribute type")
+            display("field type `{}` borrows a raw attribute, it must be a reference, an `Option` or a `Vec` and cannot have a `flag_value`, `separator` or be split from another attribute", Q(&ty))
+        }
+
+        RangeTy(ty: syn::Ty) {
+            description("only integer and float fields can have a `min` or `max`")
+            display("field type `{}` with a `min` or `max` must contain integer or float values", Q(&ty))
+        }
+
+        RangeValue(value: String, ty: syn::Ty) {
+            description("invalid `min` or `max` bound")
+            display("bound `{}` is not a valid value of field type `{}`", value, Q(&ty))
+        }
+
+        LenTy(ty: syn::Ty) {
+            description("only string and collection fields can have a `min_len`, `max_len` or `non_empty`")
+            display("field type `{}` with a `min_len`, `max_len` or `non_empty` must be a string or a `Vec`, `HashSet`, `BTreeSet`, `HashMap` or `BTreeMap`", Q(&ty))
+        }
+
+        MapKeyTy(ty: syn::Ty) {
+            description("unsupported map key type")
+            display("map key type `{}` is not supported, keys are parsed from the attribute names", Q(&ty))
+        }
+
+        MapOptions {
+            description("map fields cannot have a `flag_value` or be split from another attribute")
         }
 
         TyRef(ty: syn::Ty) {
             description("unsupported reference type")
-            display("type `{}` is not supported, only immutable `str` and `[u8]` reference types are supported", Q(&ty))
+            display("type `{}` is not supported, only immutable `str`, `[u8]`, `syn::Lit`, `syn::MetaItem` and `[syn::NestedMetaItem]` reference types are supported", Q(&ty))
+        }
+
+        NestedTy(ty: syn::Ty) {
+            description("unsupported nested type")
+            display("type `{}` cannot be nested, only other attribute types are supported", Q(&ty))
+        }
+
+        Attributes(errors: Vec<String>) {
+            description("invalid attributes")
+            display("invalid attributes: {}", errors.join(", "))
+        }
+
+        CaptureTy(ty: syn::Ty) {
+            description("captured fields must be an Option")
+            display("captured field type `{}` must be an `Option`", Q(&ty))
+        }
+
+        VariantCapture {
+            description("captures are only supported on struct fields")
         }
 
         DocsTy(field: syn::Field) {
             description("docs field must be a Vec<&str>")
             display("docs field `{}` must be a Vec<&str>", field.ident.as_ref().unwrap())
         }
+
+        ConstraintField(name: String) {
+            description("constraint refers to an unknown field")
+            display("constraint refers to unknown field `{}`", name)
+        }
+
+        RestMissing(name: String) {
+            description("rest field not found")
+            display("rest field `{}` was not found", name)
+        }
+
+        RestTy(field: syn::Field) {
+            description("rest field must be a Vec<&syn::MetaItem> or Vec<syn::MetaItem>")
+            display("rest field `{}` must be a Vec<&syn::MetaItem> or Vec<syn::MetaItem>", field.ident.as_ref().unwrap())
+        }
     }
 }
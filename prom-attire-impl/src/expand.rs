@@ -1,8 +1,9 @@
 use syn;
 use quote::{Tokens, ToTokens};
 
-use dissect::{Struct, Field, SplitFields, Wrapper, Ty, Lit};
-use {Config, Defaulted};
+use dissect::{Struct, Enum, ValueEnum, Variant, VariantKind, Field, SplitFields, Constraint,
+              CaptureField, RestField, Wrapper, MapKind, Ty, Lit, SynTy, Borrowed};
+use {Config, Defaulted, Capture, DuplicatePolicy};
 
 struct Context<'a> {
     config: &'a Config<'a>,
@@ -11,36 +12,191 @@ struct Context<'a> {
     life: Tokens,
     /// A lifetime bound, using the lifetime `life`
     life_bound: Tokens,
-    /// A lifetime bound if the struct has a lifetime bound, if it is Some it
-    /// uses the lifetime from `life`
-    opt_life_bound: Option<Tokens>,
+    /// The struct's generics plus `life` if it was generated
+    impl_generics: Tokens,
+    /// The struct's generics as used in its type
+    ty_generics: Tokens,
+    /// The struct's where clause plus the bounds for its type parameters
+    where_clause: Tokens,
+    ty_params: Vec<&'a syn::Ident>,
+    /// The crate providing runtime support for the generated code
+    krate: syn::Ident,
+    /// The path to the `FromAttributes` trait
+    trait_path: Tokens,
     scope_lit: Tokens,
+    vis: &'a syn::Visibility,
     error_ty: syn::Ident,
+    warning_ty: syn::Ident,
 }
 
 impl<'a> Context<'a> {
-    fn new(strukt: &'a Struct, config: &'a Config) -> Context<'a> {
-        let opt_life_bound = strukt.lifetime.as_ref().map(|l| quote!(<#l>));
-        let life = strukt.lifetime
+    fn new(
+        ast: &'a syn::DeriveInput,
+        lifetime: Option<&'a syn::Lifetime>,
+        config: &'a Config
+    ) -> Context<'a> {
+        let life = lifetime
             .as_ref()
             .map(|l| quote!(#l))
             .unwrap_or_else(|| quote!('a));
         let life_bound = quote!(<#life>);
+        let krate = syn::Ident::new(config.krate);
+
+        let mut generics = ast.generics.clone();
+        if lifetime.is_none() {
+            generics.lifetimes.insert(0, syn::LifetimeDef::new("'a"));
+        }
+        generics.where_clause = syn::WhereClause::none();
+        let (impl_generics, _, _) = generics.split_for_impl();
+        let (_, ty_generics, _) = ast.generics.split_for_impl();
 
         Context {
             config: config,
-            strukt_ty: &strukt.ast.ident,
+            strukt_ty: &ast.ident,
             life: life,
             life_bound: life_bound,
-            opt_life_bound: opt_life_bound,
+            impl_generics: quote!(#impl_generics),
+            ty_generics: quote!(#ty_generics),
+            where_clause: quote!(),
+            ty_params: ast.generics.ty_params.iter().map(|p| &p.ident).collect(),
+            trait_path: quote!(::#krate::FromAttributes),
+            krate: krate,
             scope_lit: match config.scope {
-                Some(scope) => quote!(Some(#scope)),
-                None => quote!(None),
+                Some(scope) => quote!(&[#scope]),
+                None => quote!(&[]),
             },
-            error_ty: syn::Ident::new(strukt.ast.ident.as_ref().to_string() +
+            vis: &ast.vis,
+            error_ty: syn::Ident::new(ast.ident.as_ref().to_string() +
                                       "FromAttributesError"),
+            warning_ty: syn::Ident::new(ast.ident.as_ref().to_string() +
+                                        "FromAttributesWarning"),
+        }
+    }
+}
+
+/// Whether `ty` refers to any of the struct's type parameters
+fn mentions_ty_params(ctx: &Context, ty: &syn::Ty) -> bool {
+    match *ty {
+        syn::Ty::Slice(ref ty)
+        | syn::Ty::Array(ref ty, _)
+        | syn::Ty::Paren(ref ty) => mentions_ty_params(ctx, ty),
+        syn::Ty::Ptr(ref ty) | syn::Ty::Rptr(_, ref ty) => {
+            mentions_ty_params(ctx, &ty.ty)
+        }
+        syn::Ty::Tup(ref tys) => tys.iter().any(|ty| mentions_ty_params(ctx, ty)),
+        syn::Ty::Path(ref qself, ref path) => {
+            if let Some(ref qself) = *qself {
+                if mentions_ty_params(ctx, &qself.ty) {
+                    return true;
+                }
+            }
+            if !path.global && ctx.ty_params.contains(&&path.segments[0].ident) {
+                return true;
+            }
+            path.segments.iter().any(|segment| match segment.parameters {
+                syn::PathParameters::AngleBracketed(ref data) => {
+                    data.types.iter().any(|ty| mentions_ty_params(ctx, ty))
+                        || data.bindings.iter().any(|binding| mentions_ty_params(ctx, &binding.ty))
+                }
+                syn::PathParameters::Parenthesized(_) => false,
+            })
+        }
+        _ => false,
+    }
+}
+
+/// The bounds needed to parse a `ty`, and any `extra` traits it must
+/// implement, if it refers to a type parameter
+fn ty_bounds(ctx: &Context, ty: &Ty, extra: &[Tokens]) -> Vec<Tokens> {
+    let Context { ref life, ref trait_path, .. } = *ctx;
+    let (ty, mut bounds) = match *ty {
+        Ty::Custom(ty) => (ty, vec![quote! {
+            #ty: ::std::str::FromStr,
+            <#ty as ::std::str::FromStr>::Err: ::std::error::Error + #life
+        }]),
+        Ty::Nested(ty) => (ty, vec![quote!(#ty: #trait_path<#life>)]),
+        Ty::Literal(_) | Ty::Syn(_, _) | Ty::Borrowed(_, _) => return vec![],
+    };
+    if !mentions_ty_params(ctx, ty) {
+        return vec![];
+    }
+    if !extra.is_empty() {
+        bounds.push(quote!(#ty: #(#extra)+*));
+    }
+    bounds
+}
+
+/// The bounds needed by a field, `split` fields are cloned from their parent
+/// attribute's value
+fn field_bounds(ctx: &Context, field: &Field, split: bool) -> Vec<Tokens> {
+    let hash = vec![quote!(::std::hash::Hash), quote!(::std::cmp::Eq)];
+    let ord = vec![quote!(::std::cmp::Ord)];
+    let mut extra = vec![];
+    if split {
+        extra.push(quote!(::std::clone::Clone));
+    }
+    match field.ty {
+        Wrapper::None(_) => {
+            if field.default == Defaulted::Yep {
+                extra.push(quote!(::std::default::Default));
+            }
+        }
+        Wrapper::Option(_) | Wrapper::Vec(_) => (),
+        Wrapper::HashSet(_) => extra.extend(hash),
+        Wrapper::BTreeSet(_) => extra.extend(ord),
+        Wrapper::Map(kind, ref key, _) => {
+            let key_extra = match kind {
+                MapKind::Hash => hash,
+                MapKind::BTree => ord,
+            };
+            let mut bounds = ty_bounds(ctx, key, &key_extra);
+            bounds.extend(ty_bounds(ctx, field.ty.inner(), &extra));
+            return bounds;
         }
     }
+    ty_bounds(ctx, field.ty.inner(), &extra)
+}
+
+fn fields_bounds(ctx: &Context, fields: &[Field], split_fields: &[SplitFields])
+    -> Vec<Tokens> {
+    fields.iter()
+        .flat_map(|field| field_bounds(ctx, field, false))
+        .chain(split_fields.iter()
+            .flat_map(|split| &split.fields)
+            .flat_map(|field| field_bounds(ctx, field, true)))
+        .collect()
+}
+
+/// The where clause for the generated impls, using the `bound` override if
+/// given rather than the `inferred` bounds. Values borrowed from the
+/// attributes must outlive any extra lifetimes to be stored in fields using
+/// them.
+fn where_clause(
+    ctx: &Context,
+    ast: &syn::DeriveInput,
+    bound: &Option<Vec<syn::WherePredicate>>,
+    inferred: Vec<Tokens>
+) -> Tokens {
+    let life = &ctx.life;
+    let declared = &ast.generics.where_clause.predicates;
+    let outlives = ast.generics.lifetimes
+        .iter()
+        .skip(1)
+        .map(|def| {
+            let lifetime = &def.lifetime;
+            quote!(#life: #lifetime)
+        })
+        .collect::<Vec<_>>();
+    let bounds = match *bound {
+        Some(ref bound) => bound.iter().map(|p| quote!(#p)).collect(),
+        None => inferred,
+    };
+    if declared.is_empty() && outlives.is_empty() && bounds.is_empty() {
+        return quote!();
+    }
+    quote! {
+        where #(#declared,)* #(#outlives,)* #(#bounds,)*
+    }
 }
 
 fn setup_docs(field: &Field) -> Tokens {
@@ -67,14 +223,58 @@ fn setup_docs(field: &Field) -> Tokens {
     }
 }
 
+fn setup_rest(rest: &RestField) -> Tokens {
+    let ident = &rest.ident;
+    quote! {
+        let mut #ident = Vec::new();
+    }
+}
+
+fn write_rest(rest: &RestField) -> Tokens {
+    let ident = &rest.ident;
+    quote! {
+        #ident: #ident,
+    }
+}
+
+fn setup_nested_docs(field: &Field) -> Tokens {
+    let ident = &field.ident;
+    quote! {
+        let #ident = Vec::new();
+    }
+}
+
 fn setup_field(field: &Field) -> Tokens {
     let ident = &field.ident;
-    match field.ty {
+    let setup_seen = seen_ident(field).map(|seen| quote! {
+        let mut #seen: Option<&::syn::MetaItem> = None;
+    });
+    let setup = match field.ty {
         Wrapper::Vec(_) => {
             quote! {
                 let mut #ident = Vec::new();
             }
         }
+        Wrapper::HashSet(_) => {
+            quote! {
+                let mut #ident = ::std::collections::HashSet::new();
+            }
+        }
+        Wrapper::BTreeSet(_) => {
+            quote! {
+                let mut #ident = ::std::collections::BTreeSet::new();
+            }
+        }
+        Wrapper::Map(MapKind::Hash, _, _) => {
+            quote! {
+                let mut #ident = ::std::collections::HashMap::new();
+            }
+        }
+        Wrapper::Map(MapKind::BTree, _, _) => {
+            quote! {
+                let mut #ident = ::std::collections::BTreeMap::new();
+            }
+        }
         Wrapper::Option(_) => {
             quote! {
                 let mut #ident = None;
@@ -92,28 +292,281 @@ fn setup_field(field: &Field) -> Tokens {
                         let mut #ident = <#ty as ::std::str::FromStr>::from_str(#value).unwrap();
                     }
                 },
-                Defaulted::Nope => unreachable!(),
+                Defaulted::Nope => {
+                    // A required field, checked for once all attributes have
+                    // been seen
+                    quote! {
+                        let mut #ident = None;
+                    }
+                }
             }
         }
+    };
+    quote! {
+        #setup
+        #setup_seen
+    }
+}
+
+/// The local recording the item whose value the field holds, used to detect
+/// duplicates, collections and maps keep every item so don't need one
+fn seen_ident(field: &Field) -> Option<syn::Ident> {
+    match field.ty {
+        ref ty if ty.is_collection() => None,
+        Wrapper::Map(..) => None,
+        _ => Some(syn::Ident::new(format!("__seen_{}", field.ident))),
     }
 }
 
 fn write_field(field: &Field) -> Tokens {
     let ident = &field.ident;
+    if field.required {
+        quote! {
+            #ident: #ident.unwrap(),
+        }
+    } else {
+        quote! {
+            #ident: #ident,
+        }
+    }
+}
+
+/// Checks that a required field was specified, `parent` is the attribute it
+/// is split from, if any
+fn check_required(ctx: &Context, field: &Field, parent: Option<&str>) -> Tokens {
+    if !field.required {
+        return quote!();
+    }
+    let error_ty = &ctx.error_ty;
+    let ident = &field.ident;
+    let attribute = &field.attribute;
+    let push_parent = parent.map(|parent| quote! {
+        scope.push(#parent);
+    });
     quote! {
-        #ident: #ident,
+        if #ident.is_none() {
+            let mut scope = attr_scope.to_vec();
+            #push_parent
+            errors.push(#error_ty::Missing {
+                scope: scope,
+                attr: #attribute,
+            });
+        }
+    }
+}
+
+/// Checks every required field of a struct or struct variant
+fn check_required_fields(
+    ctx: &Context,
+    fields: &[Field],
+    split_fields: &[SplitFields]
+) -> Vec<Tokens> {
+    fields.iter()
+        .map(|field| check_required(ctx, field, None))
+        .chain(split_fields.iter().flat_map(|split| {
+            split.fields
+                .iter()
+                .map(move |field| check_required(ctx, field, Some(&split.parent)))
+        }))
+        .collect()
+}
+
+/// Whether the field's attribute was specified with a value that passed its
+/// checks, collections only count as specified if they gained a value
+fn is_present(field: &Field) -> Tokens {
+    match seen_ident(field) {
+        Some(seen) => quote!(#seen.is_some()),
+        None => {
+            let ident = &field.ident;
+            quote!(!#ident.is_empty())
+        }
+    }
+}
+
+/// Checks the constraints between the fields of a struct or struct variant
+fn check_constraints(
+    ctx: &Context,
+    fields: &[Field],
+    split_fields: &[SplitFields],
+    constraints: &[Constraint]
+) -> Vec<Tokens> {
+    let error_ty = &ctx.error_ty;
+    let find = |ident: &syn::Ident| {
+        fields.iter()
+            .chain(split_fields.iter().flat_map(|split| &split.fields))
+            .find(|field| field.ident == ident)
+            .expect("constraints only refer to known fields")
+    };
+    constraints.iter()
+        .map(|constraint| match *constraint {
+            Constraint::Exclusive(ref idents) => {
+                let checks = idents.iter()
+                    .map(|ident| {
+                        let field = find(ident);
+                        let present = is_present(field);
+                        let attribute = &field.attribute;
+                        quote!((#present, #attribute))
+                    })
+                    .collect::<Vec<_>>();
+                quote! {
+                    {
+                        let present = [#(#checks),*]
+                            .iter()
+                            .filter(|check| check.0)
+                            .map(|check| check.1)
+                            .collect::<Vec<&'static str>>();
+                        if present.len() > 1 {
+                            errors.push(#error_ty::Conflicting {
+                                scope: attr_scope.to_vec(),
+                                first: present[0],
+                                second: present[1],
+                            });
+                        }
+                    }
+                }
+            }
+            Constraint::AtLeastOne(ref idents) => {
+                let fields = idents.iter().map(|ident| find(ident)).collect::<Vec<_>>();
+                let present = fields.iter().map(|field| is_present(field));
+                let attributes = fields.iter().map(|field| &field.attribute);
+                quote! {
+                    if !(#(#present)||*) {
+                        errors.push(#error_ty::MissingOneOf {
+                            scope: attr_scope.to_vec(),
+                            attrs: &[#(#attributes),*],
+                        });
+                    }
+                }
+            }
+            Constraint::Requires(field, required) => {
+                let (field, required) = (find(field), find(required));
+                let (field_present, required_present) =
+                    (is_present(field), is_present(required));
+                let (attribute, required) = (&field.attribute, &required.attribute);
+                quote! {
+                    if #field_present && !(#required_present) {
+                        errors.push(#error_ty::Requires {
+                            scope: attr_scope.to_vec(),
+                            attr: #attribute,
+                            required: #required,
+                        });
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// The scope is recorded while parsing, other details are only available in
+/// the `from_*` methods
+fn setup_capture(ctx: &Context, capture: &CaptureField) -> Tokens {
+    let Context { ref life, .. } = *ctx;
+    let ident = &capture.ident;
+    match capture.capture {
+        Capture::Scope => quote! {
+            let mut #ident: Option<&#life str> = None;
+        },
+        Capture::Ident | Capture::Vis | Capture::Ty | Capture::Generics => {
+            quote!()
+        }
+    }
+}
+
+fn write_capture(capture: &CaptureField) -> Tokens {
+    let ident = &capture.ident;
+    match capture.capture {
+        Capture::Scope => quote! {
+            #ident: #ident,
+        },
+        Capture::Ident | Capture::Vis | Capture::Ty | Capture::Generics => {
+            quote! {
+                #ident: None,
+            }
+        }
+    }
+}
+
+/// Generates a `from_*` method parsing the attributes of `input` then storing
+/// the details of it that are `available` in the capture fields
+fn capture_method<F: Fn(Capture) -> Option<Tokens>>(
+    ctx: &Context,
+    method: &str,
+    input_ty: &str,
+    captures: &[CaptureField],
+    available: F
+) -> Tokens {
+    let Context { ref life, ref trait_path, .. } = *ctx;
+    let method = syn::Ident::new(method);
+    let input_ty = syn::Ident::new(input_ty);
+    let writes = captures.iter()
+        .filter_map(|capture| {
+            let ident = &capture.ident;
+            available(capture.capture).map(|value| quote! {
+                value.#ident = #value;
+            })
+        });
+    quote! {
+        #[allow(unused_mut)]
+        fn #method(input: &#life ::syn::#input_ty)
+            -> ::std::result::Result<Self, ::std::vec::Vec<Self::Error>>
+        {
+            let mut value = <Self as #trait_path>::try_from(&input.attrs)?;
+            #(#writes)*
+            Ok(value)
+        }
+    }
+}
+
+fn capture_methods(ctx: &Context, captures: &[CaptureField]) -> Tokens {
+    if captures.is_empty() {
+        return quote!();
+    }
+    let from_derive_input = capture_method(
+        ctx,
+        "from_derive_input",
+        "DeriveInput",
+        captures,
+        |capture| match capture {
+            Capture::Ident => Some(quote!(Some(&input.ident))),
+            Capture::Vis => Some(quote!(Some(&input.vis))),
+            Capture::Generics => Some(quote!(Some(&input.generics))),
+            Capture::Ty | Capture::Scope => None,
+        });
+    let from_field = capture_method(
+        ctx,
+        "from_field",
+        "Field",
+        captures,
+        |capture| match capture {
+            Capture::Ident => Some(quote!(input.ident.as_ref())),
+            Capture::Vis => Some(quote!(Some(&input.vis))),
+            Capture::Ty => Some(quote!(Some(&input.ty))),
+            Capture::Generics | Capture::Scope => None,
+        });
+    let from_variant = capture_method(
+        ctx,
+        "from_variant",
+        "Variant",
+        captures,
+        |capture| match capture {
+            Capture::Ident => Some(quote!(Some(&input.ident))),
+            _ => None,
+        });
+    quote! {
+        #from_derive_input
+        #from_field
+        #from_variant
     }
 }
 
 fn match_error(ctx: &Context, ty: &Ty) -> Tokens {
-    let scope_lit = &ctx.scope_lit;
     let error_ty = &ctx.error_ty;
     quote! {
         _ => {
             errors.push(#error_ty::LiteralTy {
                 value: value,
                 ty: stringify!(#ty),
-                scope: #scope_lit,
+                scope: attr_scope.to_vec(),
                 attr: ident.as_ref(),
             });
             continue;
@@ -121,8 +574,9 @@ fn match_error(ctx: &Context, ty: &Ty) -> Tokens {
     }
 }
 
-fn match_parse(ctx: &Context, ty: &Ty) -> Tokens {
-    let scope_lit = &ctx.scope_lit;
+/// Parses the string `value` into `ty`, `index` is the position of the value
+/// within the attribute if it has multiple elements
+fn match_parse(ctx: &Context, ty: &Ty, index: &Tokens) -> Tokens {
     let error_ty = &ctx.error_ty;
     match *ty {
         Ty::Literal(Lit::Str) => {
@@ -135,10 +589,11 @@ fn match_parse(ctx: &Context, ty: &Ty) -> Tokens {
             quote! {
                 if value.len() != 1 {
                     errors.push(#error_ty::Parsing {
-                        value: value,
+                        value: value.into(),
                         ty: stringify!(#ty),
-                        scope: #scope_lit,
+                        scope: attr_scope.to_vec(),
                         attr: ident.as_ref(),
+                        index: #index,
                         err: Box::new(#error_ty::ParsingChar),
                     });
                     continue;
@@ -149,19 +604,20 @@ fn match_parse(ctx: &Context, ty: &Ty) -> Tokens {
 
         Ty::Literal(Lit::ByteStr) => {
             let is_ascii = if cfg!(is_ascii_inherent) {
-                quote! { value.as_str().is_ascii() }
+                quote! { value[..].is_ascii() }
             } else {
-                quote! { ::std::ascii::AsciiExt::is_ascii(value.as_str()) }
+                quote! { ::std::ascii::AsciiExt::is_ascii(&value[..]) }
             };
             quote! {
                 if #is_ascii {
                     value.as_bytes()
                 } else {
                     errors.push(#error_ty::Parsing {
-                        value: value,
+                        value: value.into(),
                         ty: stringify!(#ty),
-                        scope: #scope_lit,
+                        scope: attr_scope.to_vec(),
                         attr: ident.as_ref(),
+                        index: #index,
                         err: Box::new(#error_ty::ParsingByteStr),
                     });
                     continue;
@@ -169,30 +625,109 @@ fn match_parse(ctx: &Context, ty: &Ty) -> Tokens {
             }
         }
 
-        ref ty => {
+        Ty::Syn(syn_ty, _) => parse_syn(ctx, ty, syn_ty, index),
+        Ty::Borrowed(Borrowed::CowStr, _) => {
             quote! {
-                #[allow(unreachable_code)]
-                match <#ty as ::std::str::FromStr>::from_str(value) {
-                    Ok(value) => value,
-                    Err(err) => {
-                        errors.push(#error_ty::Parsing {
-                            value: value,
-                            ty: stringify!(#ty),
-                            scope: #scope_lit,
-                            attr: ident.as_ref(),
-                            err: Box::new(err) as _,
-                        });
-                        continue;
-                    }
-                }
+                ::std::borrow::Cow::Borrowed(&value[..])
             }
         }
+        Ty::Borrowed(_, _) => unreachable!("raw values are not parsed"),
+        ref ty => from_str(ctx, ty, index),
     }
 }
 
-fn match_literal(ctx: &Context, ty: &Ty, lit: Lit) -> Tokens {
-    let scope_lit = &ctx.scope_lit;
+/// Parses `value` with the `syn` parser for the type
+fn parse_syn(ctx: &Context, ty: &Ty, syn_ty: SynTy, index: &Tokens) -> Tokens {
     let error_ty = &ctx.error_ty;
+    let krate = &ctx.krate;
+    let parser = match syn_ty {
+        SynTy::Path => quote!(::syn::parse_path),
+        SynTy::Ty => quote!(::syn::parse_type),
+        SynTy::Ident => quote!(::syn::parse_ident),
+        SynTy::Expr => quote!(::syn::parse_expr),
+        SynTy::WherePredicate => {
+            quote!(::#krate::runtime::parse_where_predicate)
+        }
+        SynTy::Lit => unreachable!("literals are not parsed"),
+    };
+    quote! {
+        match #parser(value) {
+            Ok(value) => value,
+            Err(err) => {
+                errors.push(#error_ty::Parsing {
+                    value: value.into(),
+                    ty: stringify!(#ty),
+                    scope: attr_scope.to_vec(),
+                    attr: ident.as_ref(),
+                    index: #index,
+                    err: Box::new(::#krate::runtime::SynError::new(err)) as _,
+                });
+                continue;
+            }
+        }
+    }
+}
+
+/// Parses `value`, which may be a `String`, `&String` or `&str`, via `FromStr`
+fn from_str(ctx: &Context, ty: &Ty, index: &Tokens) -> Tokens {
+    let error_ty = &ctx.error_ty;
+    quote! {
+        #[allow(unreachable_code)]
+        match <#ty as ::std::str::FromStr>::from_str(&value) {
+            Ok(value) => value,
+            Err(err) => {
+                errors.push(#error_ty::Parsing {
+                    value: value.into(),
+                    ty: stringify!(#ty),
+                    scope: attr_scope.to_vec(),
+                    attr: ident.as_ref(),
+                    index: #index,
+                    err: Box::new(err) as _,
+                });
+                continue;
+            }
+        }
+    }
+}
+
+/// Custom types are parsed via `FromStr` from the textual form of any
+/// non-string literals too, e.g. `#[timeout = 30]`
+fn match_custom_literals(ctx: &Context, ty: &Ty, index: &Tokens) -> Tokens {
+    let parse = from_str(ctx, ty, index);
+    quote! {
+        ::syn::Lit::Int(value, _) => {
+            let value = value.to_string();
+            #parse
+        }
+        ::syn::Lit::Float(ref value, _) => {
+            #parse
+        }
+        ::syn::Lit::Bool(value) => {
+            let value = if value { "true" } else { "false" };
+            #parse
+        }
+    }
+}
+
+/// A `Cow<str>` takes the textual form of any non-string literals
+fn match_cow_literals() -> Tokens {
+    quote! {
+        ::syn::Lit::Char(value) => {
+            ::std::borrow::Cow::Owned(value.to_string())
+        }
+        ::syn::Lit::Int(value, _) => {
+            ::std::borrow::Cow::Owned(value.to_string())
+        }
+        ::syn::Lit::Float(ref value, _) => {
+            ::std::borrow::Cow::Borrowed(value.as_str())
+        }
+        ::syn::Lit::Bool(value) => {
+            ::std::borrow::Cow::Borrowed(if value { "true" } else { "false" })
+        }
+    }
+}
+
+fn match_literal(ctx: &Context, ty: &Ty, lit: Lit, index: &Tokens) -> Tokens {
     match lit {
         Lit::Bool => {
             quote! { ::syn::Lit::Bool(value) => { value } }
@@ -211,106 +746,276 @@ fn match_literal(ctx: &Context, ty: &Ty, lit: Lit) -> Tokens {
             quote! { ::syn::Lit::ByteStr(ref value, _) => { value.as_ref() } }
         }
         Lit::Float(_) => {
+            let parse = from_str(ctx, ty, index);
             quote! {
                 ::syn::Lit::Float(ref value, _) => {
-                    #[allow(unreachable_code)]
-                    match <#ty as ::std::str::FromStr>::from_str(value.as_str()) {
-                        Ok(value) => value,
-                        Err(err) => {
-                            errors.push(#error_ty::Parsing {
-                                value: value,
-                                ty: stringify!(#ty),
-                                scope: #scope_lit,
-                                attr: ident.as_ref(),
-                                err: Box::new(err) as _,
-                            });
-                            continue;
-                        }
-                    }
+                    #parse
                 }
             }
         }
     }
 }
 
-fn match_write(field: &Field) -> Tokens {
-    let ident = &field.ident;
-    match field.ty {
-        Wrapper::Vec(_) => {
-            quote! {
-                #ident.push(value);
+fn match_value(ctx: &Context, ty: &Ty, index: &Tokens) -> Tokens {
+    match *ty {
+        Ty::Syn(SynTy::Lit, _) => return quote!(value.clone()),
+        Ty::Borrowed(Borrowed::Lit, _) => return quote!(value),
+        _ => (),
+    }
+    let error = match_error(ctx, ty);
+    let parse = match_parse(ctx, ty, index);
+    let literal = match *ty {
+        Ty::Custom(_) => Some(match_custom_literals(ctx, ty, index)),
+        Ty::Borrowed(Borrowed::CowStr, _) => Some(match_cow_literals()),
+        _ => ty.lit().map(|lit| match_literal(ctx, ty, lit, index)),
+    };
+    quote! {
+        match *value {
+            ::syn::Lit::Str(ref value, _) => {
+                #parse
             }
+            #literal
+            #error
         }
-        Wrapper::Option(_) => {
-            quote! {
-                #ident = Some(value);
-            }
+    }
+}
+
+fn match_write(ctx: &Context, field: &Field) -> Tokens {
+    write_value(ctx, field, quote!(value))
+}
+
+/// Bounds to check a value against, written as literals with `suffix`
+struct Bounds<'b> {
+    min: Option<&'b str>,
+    max: Option<&'b str>,
+    suffix: Tokens,
+    /// Whether the value checked is the length of the attribute's value
+    length: bool,
+}
+
+/// Pushes an `OutOfRange` error if `value` is outside `bounds`, otherwise
+/// runs `within`
+fn check_bounds(
+    ctx: &Context,
+    scope: &Tokens,
+    attr: &Tokens,
+    value: &Tokens,
+    bounds: Bounds,
+    within: Option<Tokens>
+) -> Tokens {
+    let error_ty = &ctx.error_ty;
+    let Bounds { min, max, ref suffix, length } = bounds;
+    let literal = |bound: &str| syn::Ident::new(format!("{}{}", bound, suffix));
+    let checks = min.map(|min| {
+            let min = literal(min);
+            quote!(#value < #min)
+        })
+        .into_iter()
+        .chain(max.map(|max| {
+            let max = literal(max);
+            quote!(#value > #max)
+        }))
+        .collect::<Vec<_>>();
+    let min = match min {
+        Some(min) => quote!(Some(#min)),
+        None => quote!(None),
+    };
+    let max = match max {
+        Some(max) => quote!(Some(#max)),
+        None => quote!(None),
+    };
+    let within = within.map(|within| quote! {
+        else {
+            #within
         }
-        Wrapper::None(_) => {
-            quote! {
-                #ident = value;
-            }
+    });
+    quote! {
+        if #(#checks)||* {
+            errors.push(#error_ty::OutOfRange {
+                scope: #scope,
+                attr: #attr,
+                value: #value.to_string(),
+                length: #length,
+                min: #min,
+                max: #max,
+            });
         }
+        #within
     }
 }
 
-fn match_special(field: &Field) -> Tokens {
-    match field.flag_value {
-        Some(value) => {
-            let attribute = &field.attribute;
-            let write = match_write(field);
-            let ty = field.ty.inner();
-            quote! {
-                ::syn::MetaItem::Word(ref ident)
-                    if ident.as_ref() == #attribute => {
-                        let value = <#ty as ::std::str::FromStr>::from_str(#value).unwrap();
-                        #write
-                    }
+/// Runs the field's checks on the value bound to `value` before `write`
+/// stores it: its bounds, then its length, then its validate function. `attr`
+/// is the attribute name reported if a check fails. Returns `None` if the
+/// field has no checks
+fn check_value(ctx: &Context, field: &Field, attr: &Tokens, write: &Tokens)
+    -> Option<Tokens> {
+    let error_ty = &ctx.error_ty;
+    let scope = quote!(attr_scope.to_vec());
+    let mut checked = None;
+    if let Some(ref path) = field.validate {
+        checked = Some(quote! {
+            match #path(&value) {
+                Ok(()) => {
+                    #write
+                }
+                Err(err) => {
+                    errors.push(#error_ty::Validation {
+                        scope: attr_scope.to_vec(),
+                        attr: Some(#attr),
+                        err: err.into(),
+                    });
+                }
             }
-        }
-        _ => quote!(),
+        });
+    }
+    let per_value = match field.ty {
+        Wrapper::None(_) | Wrapper::Option(_) => true,
+        Wrapper::Vec(_)
+        | Wrapper::HashSet(_)
+        | Wrapper::BTreeSet(_)
+        | Wrapper::Map(..) => false,
+    };
+    if per_value && (field.min_len.is_some() || field.max_len.is_some()) {
+        let min_len = field.min_len.map(|min_len| min_len.to_string());
+        let max_len = field.max_len.map(|max_len| max_len.to_string());
+        let bounds = Bounds {
+            min: min_len.as_ref().map(|s| &s[..]),
+            max: max_len.as_ref().map(|s| &s[..]),
+            suffix: quote!(usize),
+            length: true,
+        };
+        let within = checked.unwrap_or_else(|| write.clone());
+        let check = check_bounds(ctx, &scope, attr, &quote!(length), bounds, Some(within));
+        let value_ty = match *field.ty.inner() {
+            Ty::Literal(Lit::Str) => Some(quote!(: &str)),
+            _ => None,
+        };
+        checked = Some(quote! {
+            let value #value_ty = value;
+            let length = value.chars().count();
+            #check
+        });
+    }
+    if field.min.is_some() || field.max.is_some() {
+        let ty = field.ty.inner();
+        let bounds = Bounds {
+            min: field.min.as_ref().map(|s| &s[..]),
+            max: field.max.as_ref().map(|s| &s[..]),
+            suffix: quote!(#ty),
+            length: false,
+        };
+        let within = checked.unwrap_or_else(|| write.clone());
+        let check = check_bounds(ctx, &scope, attr, &quote!(value), bounds, Some(within));
+        // The type of a parsed value is otherwise only inferred from where
+        // it is stored
+        checked = Some(quote! {
+            let value: #ty = value;
+            #check
+        });
     }
+    checked
 }
 
-fn match_field(ctx: &Context, field: &Field) -> Tokens {
+/// Checks the number of elements in a collection field, `parent` is the
+/// attribute it is split from, if any
+fn check_length(ctx: &Context, field: &Field, parent: Option<&str>) -> Tokens {
+    let collection = match field.ty {
+        Wrapper::None(_) | Wrapper::Option(_) => false,
+        Wrapper::Vec(_)
+        | Wrapper::HashSet(_)
+        | Wrapper::BTreeSet(_)
+        | Wrapper::Map(..) => true,
+    };
+    if !collection || (field.min_len.is_none() && field.max_len.is_none()) {
+        return quote!();
+    }
+    let ident = &field.ident;
     let attribute = &field.attribute;
-    let error = match_error(ctx, field.ty.inner());
-    let parse = match_parse(ctx, field.ty.inner());
-    let literal = field.ty
-        .inner()
-        .lit()
-        .map(|lit| match_literal(ctx, field.ty.inner(), lit));
-    let write = match_write(field);
-    let special = match_special(field);
+    let push_parent = parent.map(|parent| quote! {
+        scope.push(#parent);
+    });
+    let scope = quote! {
+        {
+            let mut scope = attr_scope.to_vec();
+            #push_parent
+            scope
+        }
+    };
+    let min_len = field.min_len.map(|min_len| min_len.to_string());
+    let max_len = field.max_len.map(|max_len| max_len.to_string());
+    let bounds = Bounds {
+        min: min_len.as_ref().map(|s| &s[..]),
+        max: max_len.as_ref().map(|s| &s[..]),
+        suffix: quote!(usize),
+        length: true,
+    };
+    let check = check_bounds(ctx, &scope, &quote!(#attribute), &quote!(length), bounds, None);
     quote! {
-        ::syn::MetaItem::NameValue(ref ident, ref value)
-            if ident.as_ref() == #attribute => {
-                let value = match *value {
-                    ::syn::Lit::Str(ref value, _) => {
-                        #parse
-                    }
-                    #literal
-                    #error
-                };
-                #write
-            }
-        #special
+        {
+            let length = #ident.len();
+            #check
+        }
     }
 }
 
-fn match_clone_write(field: &Field, ty: &Wrapper) -> Tokens {
-    let ident = &field.ident;
-    let value = match *ty.inner() {
-        Ty::Literal(_) => quote!(value),
-        Ty::Custom(_) => quote!(value.clone()),
+/// Checks the length of every collection field of a struct or struct variant
+fn check_lengths(
+    ctx: &Context,
+    fields: &[Field],
+    split_fields: &[SplitFields]
+) -> Vec<Tokens> {
+    fields.iter()
+        .map(|field| check_length(ctx, field, None))
+        .chain(split_fields.iter().flat_map(|split| {
+            split.fields
+                .iter()
+                .map(move |field| check_length(ctx, field, Some(&split.parent)))
+        }))
+        .collect()
+}
+
+/// Runs the struct or enum's validate function, if any, on the finished
+/// `value`
+fn validate_item(ctx: &Context, validate: &Option<syn::Path>) -> Tokens {
+    let path = match *validate {
+        Some(ref path) => path,
+        None => return quote!(),
     };
-    match *ty {
+    let error_ty = &ctx.error_ty;
+    quote! {
+        if let Err(err) = #path(&value) {
+            errors.push(#error_ty::Validation {
+                scope: attr_scope.to_vec(),
+                attr: None,
+                err: err.into(),
+            });
+            return Err(errors);
+        }
+    }
+}
+
+/// Stores `value` in the field
+fn store_value(field: &Field, value: Tokens) -> Tokens {
+    let ident = &field.ident;
+    match field.ty {
         Wrapper::Vec(_) => {
             quote! {
                 #ident.push(#value);
             }
         }
-        Wrapper::Option(_) => {
+        Wrapper::HashSet(_) | Wrapper::BTreeSet(_) => {
+            quote! {
+                #ident.insert(#value);
+            }
+        }
+        Wrapper::Map(..) => unreachable!("maps are written by match_map"),
+        Wrapper::Option(_) => {
+            quote! {
+                #ident = Some(#value);
+            }
+        }
+        Wrapper::None(_) if field.required => {
             quote! {
                 #ident = Some(#value);
             }
@@ -323,134 +1028,705 @@ fn match_clone_write(field: &Field, ty: &Wrapper) -> Tokens {
     }
 }
 
+/// Writes `value` into the field, checking the item it came from (bound to
+/// `item`) against any previous item according to the field's
+/// `DuplicatePolicy`
+fn write_value(ctx: &Context, field: &Field, value: Tokens) -> Tokens {
+    // Only a value that passes the field's checks counts as seen
+    let seen = seen_ident(field);
+    let mark = seen.as_ref().map(|seen| quote! {
+        #seen = Some(item);
+    });
+    let store = store_value(field, quote!(value));
+    let store = quote! {
+        #mark
+        #store
+    };
+    let checked = check_value(ctx, field, &quote!(item.name()), &store)
+        .unwrap_or(store);
+    let write = quote! {
+        let value = #value;
+        #checked
+    };
+    let seen = match seen {
+        Some(seen) => seen,
+        None => return write,
+    };
+    match field.duplicate {
+        DuplicatePolicy::Error => {
+            let error_ty = &ctx.error_ty;
+            quote! {
+                if let Some(first) = #seen {
+                    errors.push(#error_ty::Duplicate {
+                        scope: attr_scope.to_vec(),
+                        attr: item.name(),
+                        first: first,
+                        second: item,
+                    });
+                } else {
+                    #write
+                }
+            }
+        }
+        DuplicatePolicy::First => {
+            let warning_ty = &ctx.warning_ty;
+            quote! {
+                if #seen.is_none() {
+                    #write
+                } else {
+                    warnings.push(#warning_ty::Duplicate {
+                        scope: attr_scope.to_vec(),
+                        attr: item.name(),
+                        overridden: false,
+                    });
+                }
+            }
+        }
+        DuplicatePolicy::Last => {
+            let warning_ty = &ctx.warning_ty;
+            quote! {
+                if #seen.is_some() {
+                    warnings.push(#warning_ty::Duplicate {
+                        scope: attr_scope.to_vec(),
+                        attr: item.name(),
+                        overridden: true,
+                    });
+                }
+                #write
+            }
+        }
+    }
+}
+
+/// Whether `name` is the field's attribute or one of its aliases
+fn is_attribute(field: &Field, name: &Tokens) -> Tokens {
+    let checks = Some(field.attribute.as_str())
+        .into_iter()
+        .chain(field.aliases.iter().cloned())
+        .chain(field.deprecated_aliases.iter().cloned())
+        .map(|attribute| quote!(#name == #attribute))
+        .collect::<Vec<_>>();
+    quote!(#(#checks)||*)
+}
+
+/// Warns that a deprecated field or one of its deprecated aliases was used,
+/// the item used is bound to `item`
+fn warn_deprecated(ctx: &Context, field: &Field) -> Tokens {
+    let warning_ty = &ctx.warning_ty;
+    let deprecated = match field.deprecated {
+        Some(note) => quote! {
+            warnings.push(#warning_ty::Deprecated {
+                scope: attr_scope.to_vec(),
+                attr: item.name(),
+                note: #note,
+            });
+        },
+        None => quote!(),
+    };
+    let note = format!("use `{}` instead", field.attribute);
+    let aliases = field.deprecated_aliases.iter().map(|alias| quote! {
+        if item.name() == #alias {
+            warnings.push(#warning_ty::Deprecated {
+                scope: attr_scope.to_vec(),
+                attr: item.name(),
+                note: #note,
+            });
+        }
+    });
+    quote! {
+        #deprecated
+        #(#aliases)*
+    }
+}
+
+fn match_special(ctx: &Context, field: &Field) -> Tokens {
+    match field.flag_value {
+        Some(value) => {
+            let is_attribute = is_attribute(field, &quote!(ident.as_ref()));
+            let deprecated = warn_deprecated(ctx, field);
+            let write = match_write(ctx, field);
+            let ty = field.ty.inner();
+            quote! {
+                ::syn::MetaItem::Word(ref ident)
+                    if #is_attribute => {
+                        #deprecated
+                        let value = <#ty as ::std::str::FromStr>::from_str(#value).unwrap();
+                        #write
+                    }
+            }
+        }
+        _ => quote!(),
+    }
+}
+
+fn match_nested_field(ctx: &Context, field: &Field, ty: &syn::Ty) -> Tokens {
+    let error_ty = &ctx.error_ty;
+    let warning_ty = &ctx.warning_ty;
+    let trait_path = &ctx.trait_path;
+    let is_attribute = is_attribute(field, &quote!(ident.as_ref()));
+    let deprecated = warn_deprecated(ctx, field);
+    let nest_scope = nest_scope();
+    let write = match_write(ctx, field);
+    quote! {
+        ::syn::MetaItem::List(ref ident, ref values)
+            if #is_attribute => {
+                #deprecated
+                #nest_scope
+                let value = match <#ty as #trait_path>::try_from_nested_with_warnings(values, attr_scope) {
+                    Ok((value, nested_warnings)) => {
+                        warnings.extend(nested_warnings.into_iter().map(|warning| {
+                            #warning_ty::Nested(Box::new(warning))
+                        }));
+                        value
+                    }
+                    Err(errs) => {
+                        errors.extend(errs.into_iter().map(|err| {
+                            #error_ty::Nested(Box::new(err))
+                        }));
+                        continue;
+                    }
+                };
+                #write
+            }
+    }
+}
+
+fn match_field(ctx: &Context, field: &Field) -> Tokens {
+    if let Ty::Nested(ty) = *field.ty.inner() {
+        return match_nested_field(ctx, field, ty);
+    }
+    if let Wrapper::Map(_, ref key, ref value) = field.ty {
+        return match_map(ctx, field, key, value);
+    }
+    if let Ty::Borrowed(borrowed, _) = *field.ty.inner() {
+        if let Some(raw) = match_raw(ctx, field, borrowed) {
+            return raw;
+        }
+    }
+    let is_attribute = is_attribute(field, &quote!(ident.as_ref()));
+    let deprecated = warn_deprecated(ctx, field);
+    let value = match_value(ctx, field.ty.inner(), &quote!(None));
+    let write = match_write(ctx, field);
+    let special = match_special(ctx, field);
+    let separated = field.separator.map(|separator| match_separated(ctx, field, separator));
+    // Custom types such as `#[derive(AttireValue)]` enums can also be given
+    // as a word, e.g. `#[mode(fast)]`
+    let list = match *field.ty.inner() {
+        _ if field.ty.is_collection() => Some(match_list(ctx, field)),
+        Ty::Custom(_) => Some(match_list(ctx, field)),
+        _ => None,
+    };
+    quote! {
+        #separated
+        ::syn::MetaItem::NameValue(ref ident, ref value)
+            if #is_attribute => {
+                #deprecated
+                let value = #value;
+                #write
+            }
+        #special
+        #list
+    }
+}
+
+/// Splits a string value on the field's separator, parsing each element
+/// separately
+fn match_separated(ctx: &Context, field: &Field, separator: &str) -> Tokens {
+    let krate = &ctx.krate;
+    let is_attribute = is_attribute(field, &quote!(ident.as_ref()));
+    let deprecated = warn_deprecated(ctx, field);
+    let parse = match_parse(ctx, field.ty.inner(), &quote!(Some(index)));
+    let write = match_write(ctx, field);
+    quote! {
+        ::syn::MetaItem::NameValue(ref ident, ::syn::Lit::Str(ref values, _))
+            if #is_attribute => {
+                #deprecated
+                for (index, value) in ::#krate::runtime::split(values, #separator).into_iter().enumerate() {
+                    let value = { #parse };
+                    #write
+                }
+            }
+    }
+}
+
+/// Raw pieces of the attribute are borrowed as is
+fn match_raw(ctx: &Context, field: &Field, borrowed: Borrowed) -> Option<Tokens> {
+    let deprecated = warn_deprecated(ctx, field);
+    let write = match_write(ctx, field);
+    match borrowed {
+        Borrowed::MetaItem => {
+            let is_attribute = is_attribute(field, &quote!(item.name()));
+            Some(quote! {
+                _ if #is_attribute => {
+                    #deprecated
+                    let value = item;
+                    #write
+                }
+            })
+        }
+        Borrowed::NestedMetaItems => {
+            let is_attribute = is_attribute(field, &quote!(ident.as_ref()));
+            Some(quote! {
+                ::syn::MetaItem::List(ref ident, ref values)
+                    if #is_attribute => {
+                        #deprecated
+                        let value = &values[..];
+                        #write
+                    }
+            })
+        }
+        Borrowed::Lit | Borrowed::CowStr => None,
+    }
+}
+
+/// Fills a map from the `key = value` items nested in the attribute, keys are
+/// parsed from the item names and checked for duplicates according to the
+/// field's `DuplicatePolicy`
+fn match_map(ctx: &Context, field: &Field, key: &Ty, value: &Ty) -> Tokens {
+    let error_ty = &ctx.error_ty;
+    let warning_ty = &ctx.warning_ty;
+    let ident = &field.ident;
+    let is_attribute = is_attribute(field, &quote!(ident.as_ref()));
+    let deprecated = warn_deprecated(ctx, field);
+    let nest_scope = nest_scope();
+    let key = match_parse(ctx, key, &quote!(None));
+    let value = match_value(ctx, value, &quote!(None));
+    let write = match field.duplicate {
+        DuplicatePolicy::Error => quote! {
+            if #ident.contains_key(&key) {
+                errors.push(#error_ty::DuplicateKey {
+                    scope: attr_scope.to_vec(),
+                    key: ident.as_ref(),
+                });
+            } else {
+                #ident.insert(key, value);
+            }
+        },
+        DuplicatePolicy::First => quote! {
+            if #ident.contains_key(&key) {
+                warnings.push(#warning_ty::DuplicateKey {
+                    scope: attr_scope.to_vec(),
+                    key: ident.as_ref(),
+                    overridden: false,
+                });
+            } else {
+                #ident.insert(key, value);
+            }
+        },
+        DuplicatePolicy::Last => quote! {
+            if #ident.insert(key, value).is_some() {
+                warnings.push(#warning_ty::DuplicateKey {
+                    scope: attr_scope.to_vec(),
+                    key: ident.as_ref(),
+                    overridden: true,
+                });
+            }
+        },
+    };
+    let write = check_value(ctx, field, &quote!(ident.as_ref()), &write).unwrap_or(write);
+    quote! {
+        ::syn::MetaItem::List(ref ident, ref values)
+            if #is_attribute => {
+                #deprecated
+                let outer_scope = attr_scope;
+                #nest_scope
+                for nested in values {
+                    let (ident, value) = match *nested {
+                        ::syn::NestedMetaItem::MetaItem(::syn::MetaItem::NameValue(ref ident, ref value)) => {
+                            (ident, value)
+                        }
+                        _ => {
+                            errors.push(#error_ty::ListItem {
+                                item: nested,
+                                ty: "`key = value` entry",
+                                scope: outer_scope.to_vec(),
+                                attr: ident.as_ref(),
+                            });
+                            continue;
+                        }
+                    };
+                    let key = {
+                        let value: &str = ident.as_ref();
+                        #key
+                    };
+                    let value = #value;
+                    #write
+                }
+            }
+    }
+}
+
+/// Collections also accept a list of values, e.g. `#[derives(Debug, Clone)]`,
+/// each of which may be a word or literal. Other fields of custom types
+/// accept a single value this way
+fn match_list(ctx: &Context, field: &Field) -> Tokens {
+    let error_ty = &ctx.error_ty;
+    let is_attribute = is_attribute(field, &quote!(ident.as_ref()));
+    let deprecated = warn_deprecated(ctx, field);
+    let ty = field.ty.inner();
+    // A list for a single value, e.g. `#[mode(fast)]`, must hold one item and
+    // errors don't report its index
+    let (index, single) = if field.ty.is_collection() {
+        (quote!(Some(index)), quote!())
+    } else {
+        let single = quote! {
+            if index > 0 {
+                errors.push(#error_ty::ListItem {
+                    item: nested,
+                    ty: concat!("single ", stringify!(#ty)),
+                    scope: attr_scope.to_vec(),
+                    attr: ident.as_ref(),
+                });
+                continue;
+            }
+        };
+        (quote!(None), single)
+    };
+    let value = match_value(ctx, ty, &index);
+    let word = match *ty {
+        Ty::Syn(SynTy::Lit, _) | Ty::Borrowed(Borrowed::Lit, _) => quote! {
+            errors.push(#error_ty::ListItem {
+                item: nested,
+                ty: stringify!(#ty),
+                scope: attr_scope.to_vec(),
+                attr: ident.as_ref(),
+            });
+            continue;
+        },
+        _ => match_parse(ctx, ty, &index),
+    };
+    let write = match_write(ctx, field);
+    quote! {
+        ::syn::MetaItem::List(ref ident, ref values)
+            if #is_attribute => {
+                #deprecated
+                for (index, nested) in values.iter().enumerate() {
+                    #single
+                    let value = match *nested {
+                        ::syn::NestedMetaItem::Literal(ref value) => {
+                            #value
+                        }
+                        ::syn::NestedMetaItem::MetaItem(::syn::MetaItem::Word(ref value)) => {
+                            let value: &str = value.as_ref();
+                            #word
+                        }
+                        _ => {
+                            errors.push(#error_ty::ListItem {
+                                item: nested,
+                                ty: stringify!(#ty),
+                                scope: attr_scope.to_vec(),
+                                attr: ident.as_ref(),
+                            });
+                            continue;
+                        }
+                    };
+                    #write
+                }
+            }
+    }
+}
+
+fn match_clone_write(ctx: &Context, field: &Field) -> Tokens {
+    let value = match *field.ty.inner() {
+        Ty::Literal(_) => quote!(value),
+        Ty::Custom(_)
+        | Ty::Nested(_)
+        | Ty::Syn(_, _)
+        | Ty::Borrowed(Borrowed::CowStr, _) => quote!(value.clone()),
+        Ty::Borrowed(_, _) => quote!(value),
+    };
+    write_value(ctx, field, value)
+}
+
 fn match_split_fields(ctx: &Context, split: &SplitFields) -> Tokens {
     let parent = &split.parent;
-    let error = match_error(ctx, split.ty.inner());
-    let parse = match_parse(ctx, split.ty.inner());
-    let literal = split.ty
-        .inner()
-        .lit()
-        .map(|lit| match_literal(ctx, split.ty.inner(), lit));
+    let value = match_value(ctx, split.ty.inner(), &quote!(None));
     let writes =
-        split.fields.iter().map(|field| match_clone_write(field, &field.ty));
+        split.fields.iter().map(|field| match_clone_write(ctx, field));
     let matches = split.fields.iter().map(|field| match_field(ctx, field));
+    let known = known_attributes(&split.fields, &[]);
+    let nest_scope = nest_scope();
+    let match_nested = match_nested(ctx, &known, None, matches);
     quote! {
         ::syn::MetaItem::NameValue(ref ident, ref value)
             if ident.as_ref() == #parent => {
-                let value = match *value {
-                    ::syn::Lit::Str(ref value, _) => {
-                        #parse
-                    }
-                    #literal
-                    #error
-                };
+                let value = #value;
                 #(#writes)*
             }
         ::syn::MetaItem::List(ref ident, ref values)
             if ident.as_ref() == #parent => {
-                for value in values {
-                    if let ::syn::NestedMetaItem::MetaItem(ref item) = *value {
-                        match *item {
-                            #(#matches)*
-                            ref item => {
-                                println!(
-                                    "Unexpected attribute under '{}' ({:?})",
-                                    #parent, item);
-                            }
-                        }
-                    }
+                #nest_scope
+                #match_nested
+            }
+    }
+}
+
+/// Pushes the attribute bound to `ident` onto the `attr_scope` used for
+/// errors while matching the items nested within it
+fn nest_scope() -> Tokens {
+    quote! {
+        let nested_scope = {
+            let mut nested_scope = attr_scope.to_vec();
+            nested_scope.push(ident.as_ref());
+            nested_scope
+        };
+        let attr_scope = nested_scope.as_slice();
+    }
+}
+
+/// The attribute names matched by the given fields, used to suggest a
+/// replacement for unknown attributes, deprecated fields are never suggested
+fn known_attributes<'b>(fields: &'b [Field], split_fields: &'b [SplitFields])
+    -> Vec<&'b str> {
+    fields.iter()
+        .filter(|field| field.deprecated.is_none())
+        .map(|field| field.attribute.as_str())
+        .chain(split_fields.iter().map(|split| split.parent.as_str()))
+        .collect()
+}
+
+/// Collects an attribute no field matched into the `rest` field
+fn match_rest(rest: &RestField) -> Tokens {
+    let ident = &rest.ident;
+    let item = if rest.owned {
+        quote!(item.clone())
+    } else {
+        quote!(item)
+    };
+    quote! {
+        ref item => {
+            #ident.push(#item);
+        }
+    }
+}
+
+/// Matches each item nested in a `MetaItem::List` whose contents are bound
+/// to `values`, `known` are the attributes that `matches` will match
+fn match_nested<I: Iterator<Item = Tokens>>(
+    ctx: &Context,
+    known: &[&str],
+    rest: Option<&RestField>,
+    matches: I
+) -> Tokens {
+    let Context { ref error_ty, ref warning_ty, ref krate, .. } = *ctx;
+    let unknown = if let Some(rest) = rest {
+        match_rest(rest)
+    } else if ctx.config.allow_unknown {
+        // The scope is shared with something else that may understand it, so
+        // only warn in case it is a typo
+        quote! {
+            ref item => {
+                warnings.push(#warning_ty::UnknownAttribute {
+                    scope: attr_scope.to_vec(),
+                    attr: item.name(),
+                    suggestion: ::#krate::runtime::suggest(item.name(), &[#(#known),*]),
+                });
+            }
+        }
+    } else {
+        quote! {
+            ref item => {
+                errors.push(#error_ty::UnknownAttribute {
+                    scope: attr_scope.to_vec(),
+                    attr: item.name(),
+                    suggestion: ::#krate::runtime::suggest(item.name(), &[#(#known),*]),
+                });
+            }
+        }
+    };
+    quote! {
+        for value in values {
+            if let ::syn::NestedMetaItem::MetaItem(ref item) = *value {
+                match *item {
+                    #(#matches)*
+                    #unknown
                 }
             }
+        }
     }
 }
 
 fn match_loop<I: Iterator<Item = Tokens>>(
     ctx: &Context,
+    known: &[&str],
+    rest: Option<&RestField>,
+    captures: &[CaptureField],
     matches: I
 ) -> Tokens {
     if let Some(scope) = ctx.config.scope {
+        let warning_ty = &ctx.warning_ty;
+        let deprecated_scopes = ctx.config.deprecated_scopes;
+        let scopes = Some(scope)
+            .into_iter()
+            .chain(ctx.config.scope_aliases.iter().cloned())
+            .chain(deprecated_scopes.iter().cloned())
+            .collect::<Vec<_>>();
+        let warn_deprecated = if deprecated_scopes.is_empty() {
+            quote!()
+        } else {
+            quote! {
+                if #(ident == #deprecated_scopes)||* {
+                    warnings.push(#warning_ty::DeprecatedScope {
+                        scope: ident.as_ref(),
+                        replacement: #scope,
+                    });
+                }
+            }
+        };
+        let record_scope = captures.iter()
+            .filter(|capture| capture.capture == Capture::Scope)
+            .map(|capture| {
+                let ident = &capture.ident;
+                quote! {
+                    #ident = #ident.or(Some(ident.as_ref()));
+                }
+            });
+        let match_nested = match_nested(ctx, known, rest, matches);
         quote! {
             for attr in attrs {
                 if let ::syn::MetaItem::List(ref ident, ref values) = attr.value {
-                    if ident == #scope {
-                        for value in values {
-                            if let ::syn::NestedMetaItem::MetaItem(ref item) = *value {
-                                match *item {
-                                    #(#matches)*
-                                    ref item => {
-                                        println!(
-                                            "Unexpected attribute under '{}' ({:?})",
-                                            #scope, item);
-                                    }
-                                }
-                            }
-                        }
+                    if #(ident == #scopes)||* {
+                        let attr_scope: &[&str] = &[ident.as_ref()];
+                        #warn_deprecated
+                        #(#record_scope)*
+                        #match_nested
                     }
                 }
             }
         }
     } else {
+        let unknown = match rest {
+            Some(rest) => match_rest(rest),
+            None => quote! {
+                _ => {
+                    // Ignore it, we're unscoped so no control over what
+                    // appears
+                }
+            },
+        };
         quote! {
             for attr in attrs {
-                match attr.value {
+                let item = &attr.value;
+                match *item {
                     #(#matches)*
-                    _ => {
-                        // Ignore it, we're unscoped so no control over what
-                        // appears
-                    }
+                    #unknown
                 }
             }
         }
     }
 }
 
-pub fn expand(strukt: &Struct, config: &Config) -> Tokens {
-    let ctx = Context::new(strukt, config);
-
-    let setup_fields = strukt.fields
-        .iter()
-        .chain(strukt.split_fields.iter().flat_map(|split| &split.fields))
-        .map(setup_field)
-        .chain(strukt.docs.as_ref().map(setup_docs));
-    let field_matches = strukt.fields
-        .iter()
-        .map(|field| match_field(&ctx, field))
-        .chain(strukt.split_fields
-            .iter()
-            .map(|split| match_split_fields(&ctx, split)));
-    let match_loop = match_loop(&ctx, field_matches);
-    let write_fields = strukt.fields
-        .iter()
-        .chain(&strukt.docs)
-        .chain(strukt.split_fields.iter().flat_map(|split| &split.fields))
-        .map(write_field);
-
-    let Context { strukt_ty,
-                  ref error_ty,
-                  ref life,
-                  ref life_bound,
-                  ref opt_life_bound,
-                  .. } = ctx;
+fn error_enum(ctx: &Context) -> Tokens {
+    let Context { vis, ref error_ty, ref life, ref life_bound, .. } = *ctx;
 
     quote! {
         #[allow(dead_code)]
         #[derive(Debug)]
-        enum #error_ty#life_bound {
+        #vis enum #error_ty#life_bound {
             LiteralTy {
                 value: &#life ::syn::Lit,
                 ty: &'static str,
-                scope: Option<&'static str>,
+                scope: Vec<&#life str>,
                 attr: &#life str,
             },
 
             Parsing {
-                value: &#life str,
+                value: ::std::borrow::Cow<#life, str>,
                 ty: &'static str,
-                scope: Option<&'static str>,
+                scope: Vec<&#life str>,
                 attr: &#life str,
+                /// The position of the value within the attribute, if it
+                /// has multiple elements
+                index: Option<usize>,
                 err: Box<::std::error::Error + #life>,
             },
 
+            ListItem {
+                item: &#life ::syn::NestedMetaItem,
+                ty: &'static str,
+                scope: Vec<&#life str>,
+                attr: &#life str,
+            },
+
+            MissingVariant {
+                scope: Vec<&#life str>,
+                variants: &'static [&'static str],
+            },
+
+            ConflictingVariants {
+                scope: Vec<&#life str>,
+                first: &#life str,
+                second: &#life str,
+            },
+
+            Missing {
+                scope: Vec<&#life str>,
+                attr: &#life str,
+            },
+
+            /// Two attributes that may not be specified together
+            Conflicting {
+                scope: Vec<&#life str>,
+                first: &#life str,
+                second: &#life str,
+            },
+
+            /// An attribute was specified without another that it requires
+            Requires {
+                scope: Vec<&#life str>,
+                attr: &#life str,
+                required: &#life str,
+            },
+
+            /// None of a group of attributes, at least one of which is
+            /// required, was specified
+            MissingOneOf {
+                scope: Vec<&#life str>,
+                attrs: &'static [&'static str],
+            },
+
+            DuplicateKey {
+                scope: Vec<&#life str>,
+                key: &#life str,
+            },
+
+            /// A value, or the length of a value if `length` is set, was
+            /// outside the bounds given for the attribute
+            OutOfRange {
+                scope: Vec<&#life str>,
+                attr: &#life str,
+                value: String,
+                length: bool,
+                min: Option<&'static str>,
+                max: Option<&'static str>,
+            },
+
+            /// A validate function rejected the value of an attribute, or the
+            /// finished attributes as a whole if `attr` is `None`
+            Validation {
+                scope: Vec<&#life str>,
+                attr: Option<&#life str>,
+                err: Box<::std::error::Error + #life>,
+            },
+
+            Duplicate {
+                scope: Vec<&#life str>,
+                attr: &#life str,
+                first: &#life ::syn::MetaItem,
+                second: &#life ::syn::MetaItem,
+            },
+
+            UnknownAttribute {
+                scope: Vec<&#life str>,
+                attr: &#life str,
+                /// The closest known attribute name, if any were close enough
+                suggestion: Option<&'static str>,
+            },
+
+            /// An error from parsing a nested attribute struct, this will
+            /// include the full path to the nested attribute
+            Nested(Box<::std::error::Error + #life>),
+
             /// This is an internal error that will only be returned as part of
             /// the `err` field on `Parsing`
             ParsingChar,
@@ -462,26 +1738,139 @@ pub fn expand(strukt: &Struct, config: &Config) -> Tokens {
 
         impl#life_bound ::std::fmt::Display for #error_ty#life_bound {
             fn fmt(&self, w: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                fn path(scope: &[&str], attr: &str) -> String {
+                    scope.iter().rev().fold(attr.to_owned(), |path, s| format!("{}({})", s, path))
+                }
+
+                fn under(scope: &[&str]) -> String {
+                    match scope.split_last() {
+                        Some((s, rest)) => format!(" under {}", path(rest, s)),
+                        None => String::new(),
+                    }
+                }
+
                 match *self {
                     #error_ty::LiteralTy { ref value, ref ty, ref scope, ref attr } => {
                         write!(
                             w,
                            "Unexpected attribute literal {:?} for {}, expected a {}",
                             value,
-                            scope.map(|s| format!("{}({})", s, attr)).unwrap_or_else(|| attr.to_string()),
+                            path(scope, attr),
                             ty)
                     }
 
-                    #error_ty::Parsing { ref value, ref ty, ref scope, ref attr, ref err } => {
+                    #error_ty::Parsing { ref value, ref ty, ref scope, ref attr, index, ref err } => {
+                        write!(w, "Parsing attribute value {:?}", value)?;
+                        if let Some(index) = index {
+                            write!(w, " (element {})", index)?;
+                        }
+                        write!(w, " into a {} for {} failed: {}", ty, path(scope, attr), err)
+                    }
+
+                    #error_ty::ListItem { ref ty, ref scope, ref attr, .. } => {
                         write!(
                             w,
-                           "Parsing attribute value {:?} into a {} for {} failed: {}",
-                            value,
-                            scope.map(|s| format!("{}({})", s, attr)).unwrap_or_else(|| attr.to_string()),
-                            ty,
+                            "Unexpected item in list attribute {}, expected a {}",
+                            path(scope, attr),
+                            ty)
+                    }
+
+                    #error_ty::MissingVariant { ref scope, ref variants } => {
+                        write!(
+                            w,
+                            "Missing attribute{}, expected one of {}",
+                            under(scope),
+                            variants.iter().map(|v| format!("`{}`", v)).collect::<Vec<_>>().join(", "))
+                    }
+
+                    #error_ty::ConflictingVariants { ref scope, ref first, ref second } => {
+                        write!(
+                            w,
+                            "Conflicting attributes {} and {}, only one may be specified",
+                            path(scope, first),
+                            path(scope, second))
+                    }
+
+                    #error_ty::Missing { ref scope, ref attr } => {
+                        write!(w, "Missing required attribute {}", path(scope, attr))
+                    }
+
+                    #error_ty::Conflicting { ref scope, ref first, ref second } => {
+                        write!(
+                            w,
+                            "Conflicting attributes {} and {}, only one may be specified",
+                            path(scope, first),
+                            path(scope, second))
+                    }
+
+                    #error_ty::Requires { ref scope, ref attr, ref required } => {
+                        write!(
+                            w,
+                            "Attribute {} requires {} to also be specified",
+                            path(scope, attr),
+                            path(scope, required))
+                    }
+
+                    #error_ty::MissingOneOf { ref scope, ref attrs } => {
+                        write!(
+                            w,
+                            "Missing attribute{}, expected at least one of {}",
+                            under(scope),
+                            attrs.iter().map(|a| format!("`{}`", a)).collect::<Vec<_>>().join(", "))
+                    }
+
+                    #error_ty::DuplicateKey { ref scope, ref key } => {
+                        write!(
+                            w,
+                            "Duplicate key {}, it may only be specified once",
+                            path(scope, key))
+                    }
+
+                    #error_ty::OutOfRange { ref scope, ref attr, ref value, length, min, max } => {
+                        if length {
+                            write!(w, "Length {} of {} is out of range", value, path(scope, attr))?;
+                        } else {
+                            write!(w, "Value {} for {} is out of range", value, path(scope, attr))?;
+                        }
+                        match (min, max) {
+                            (Some(min), Some(max)) => write!(w, ", expected between {} and {}", min, max),
+                            (Some(min), None) => write!(w, ", expected at least {}", min),
+                            (None, Some(max)) => write!(w, ", expected at most {}", max),
+                            (None, None) => Ok(()),
+                        }
+                    }
+
+                    #error_ty::Validation { ref scope, attr: Some(ref attr), ref err } => {
+                        write!(w, "Invalid attribute {}: {}", path(scope, attr), err)
+                    }
+
+                    #error_ty::Validation { ref scope, attr: None, ref err } => {
+                        write!(
+                            w,
+                            "Invalid attributes{}: {}",
+                            under(scope),
                             err)
                     }
 
+                    #error_ty::Duplicate { ref scope, ref attr, .. } => {
+                        write!(
+                            w,
+                            "Duplicate attribute {}, it may only be specified once",
+                            path(scope, attr))
+                    }
+
+                    #error_ty::UnknownAttribute { ref scope, ref attr, ref suggestion } => {
+                        write!(w, "Unknown attribute `{}`", path(scope, attr))?;
+                        if let Some(suggestion) = *suggestion {
+                            write!(w, ", did you mean `{}`?", suggestion)?;
+                        }
+                        Ok(())
+                    }
+
+                    #error_ty::Nested(ref err) => {
+                        ::std::fmt::Display::fmt(err, w)
+                    }
+
                     #error_ty::ParsingChar => {
                         write!(w, "expected one character")
                     }
@@ -498,6 +1887,19 @@ pub fn expand(strukt: &Struct, config: &Config) -> Tokens {
                 match *self {
                     #error_ty::LiteralTy { .. } => "Unexpected attribute literal",
                     #error_ty::Parsing { .. } => "Parsing attribute value failed",
+                    #error_ty::ListItem { .. } => "Unexpected item in list attribute",
+                    #error_ty::MissingVariant { .. } => "Missing attribute",
+                    #error_ty::ConflictingVariants { .. } => "Conflicting attributes",
+                    #error_ty::Missing { .. } => "Missing required attribute",
+                    #error_ty::Conflicting { .. } => "Conflicting attributes",
+                    #error_ty::Requires { .. } => "Missing attribute required by another",
+                    #error_ty::MissingOneOf { .. } => "Missing attribute",
+                    #error_ty::DuplicateKey { .. } => "Duplicate key",
+                    #error_ty::OutOfRange { .. } => "Attribute value out of range",
+                    #error_ty::Validation { .. } => "Attribute validation failed",
+                    #error_ty::Duplicate { .. } => "Duplicate attribute",
+                    #error_ty::UnknownAttribute { .. } => "Unknown attribute",
+                    #error_ty::Nested(ref err) => err.description(),
                     #error_ty::ParsingChar => "expected one character",
                     #error_ty::ParsingByteStr => "non-ascii character",
                 }
@@ -507,33 +1909,454 @@ pub fn expand(strukt: &Struct, config: &Config) -> Tokens {
                 match *self {
                     #error_ty::LiteralTy { .. } => None,
                     #error_ty::Parsing { ref err, .. } => Some(&**err),
+                    #error_ty::ListItem { .. } => None,
+                    #error_ty::MissingVariant { .. } => None,
+                    #error_ty::ConflictingVariants { .. } => None,
+                    #error_ty::Missing { .. } => None,
+                    #error_ty::Conflicting { .. } => None,
+                    #error_ty::Requires { .. } => None,
+                    #error_ty::MissingOneOf { .. } => None,
+                    #error_ty::DuplicateKey { .. } => None,
+                    #error_ty::OutOfRange { .. } => None,
+                    #error_ty::Validation { ref err, .. } => Some(&**err),
+                    #error_ty::Duplicate { .. } => None,
+                    #error_ty::UnknownAttribute { .. } => None,
+                    #error_ty::Nested(ref err) => err.cause(),
                     #error_ty::ParsingChar => None,
                     #error_ty::ParsingByteStr => None,
                 }
             }
         }
+    }
+}
+
+fn warning_enum(ctx: &Context) -> Tokens {
+    let Context { vis, ref warning_ty, ref life, ref life_bound, ref krate, .. } = *ctx;
+
+    quote! {
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        #vis enum #warning_ty#life_bound {
+            Deprecated {
+                scope: Vec<&#life str>,
+                attr: &#life str,
+                /// What to use instead
+                note: &'static str,
+            },
+
+            DeprecatedScope {
+                scope: &#life str,
+                replacement: &'static str,
+            },
+
+            /// An attribute in a shared scope that no field matched
+            UnknownAttribute {
+                scope: Vec<&#life str>,
+                attr: &#life str,
+                /// The closest known attribute name, if any were close enough
+                suggestion: Option<&'static str>,
+            },
+
+            /// An attribute specified more than once, `overridden` is whether
+            /// the later value replaced the earlier one
+            Duplicate {
+                scope: Vec<&#life str>,
+                attr: &#life str,
+                overridden: bool,
+            },
+
+            /// A map key specified more than once, `overridden` is whether
+            /// the later value replaced the earlier one
+            DuplicateKey {
+                scope: Vec<&#life str>,
+                key: &#life str,
+                overridden: bool,
+            },
+
+            /// A warning from parsing a nested attribute struct, this will
+            /// include the full path to the nested attribute
+            Nested(Box<::#krate::Warning + #life>),
+        }
+
+        impl#life_bound ::std::fmt::Display for #warning_ty#life_bound {
+            fn fmt(&self, w: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                fn path(scope: &[&str], attr: &str) -> String {
+                    scope.iter().rev().fold(attr.to_owned(), |path, s| format!("{}({})", s, path))
+                }
+
+                match *self {
+                    #warning_ty::Deprecated { ref scope, ref attr, ref note } => {
+                        write!(w, "Attribute {} is deprecated, {}", path(scope, attr), note)
+                    }
+
+                    #warning_ty::DeprecatedScope { ref scope, ref replacement } => {
+                        write!(
+                            w,
+                            "Attribute scope `{}` is deprecated, use `{}` instead",
+                            scope,
+                            replacement)
+                    }
+
+                    #warning_ty::UnknownAttribute { ref scope, ref attr, ref suggestion } => {
+                        write!(w, "Unknown attribute `{}` ignored", path(scope, attr))?;
+                        if let Some(suggestion) = *suggestion {
+                            write!(w, ", did you mean `{}`?", suggestion)?;
+                        }
+                        Ok(())
+                    }
+
+                    #warning_ty::Duplicate { ref scope, ref attr, overridden } => {
+                        write!(
+                            w,
+                            "Duplicate attribute {}, {}",
+                            path(scope, attr),
+                            if overridden { "the last value is used" } else { "only the first value is used" })
+                    }
+
+                    #warning_ty::DuplicateKey { ref scope, ref key, overridden } => {
+                        write!(
+                            w,
+                            "Duplicate key {}, {}",
+                            path(scope, key),
+                            if overridden { "the last value is used" } else { "only the first value is used" })
+                    }
+
+                    #warning_ty::Nested(ref warning) => {
+                        ::std::fmt::Display::fmt(warning, w)
+                    }
+                }
+            }
+        }
+    }
+}
 
-        impl#opt_life_bound #strukt_ty#opt_life_bound {
-            #[allow(unused_mut)]
-            fn try_from(attrs: &[::syn::Attribute])
-                -> ::std::result::Result<#strukt_ty, ::std::vec::Vec<#error_ty>>
+fn match_variant(ctx: &Context, variant: &Variant) -> Tokens {
+    let Context { strukt_ty, ref error_ty, .. } = *ctx;
+    let attribute = &variant.attribute;
+    let ident = variant.ident;
+    let record = quote! {
+        if let Some((first, _)) = found {
+            errors.push(#error_ty::ConflictingVariants {
+                scope: attr_scope.to_vec(),
+                first: first,
+                second: ident.as_ref(),
+            });
+            continue;
+        }
+        found = Some((ident.as_ref(), value));
+    };
+    match variant.kind {
+        VariantKind::Word => {
+            quote! {
+                ::syn::MetaItem::Word(ref ident)
+                    if ident.as_ref() == #attribute => {
+                        let value = #strukt_ty::#ident;
+                        #record
+                    }
+            }
+        }
+        VariantKind::NameValue(ref ty) => {
+            let value = match_value(ctx, ty, &quote!(None));
+            quote! {
+                ::syn::MetaItem::NameValue(ref ident, ref value)
+                    if ident.as_ref() == #attribute => {
+                        let value = #strukt_ty::#ident(#value);
+                        #record
+                    }
+            }
+        }
+        VariantKind::List(ref fields, ref split_fields, ref constraints) => {
+            let setup_fields = fields.iter()
+                .chain(split_fields.iter().flat_map(|split| &split.fields))
+                .map(setup_field);
+            let field_matches = fields.iter()
+                .map(|field| match_field(ctx, field))
+                .chain(split_fields.iter()
+                    .map(|split| match_split_fields(ctx, split)));
+            let known = known_attributes(fields, split_fields);
+            let nest_scope = nest_scope();
+            let match_nested = match_nested(ctx, &known, None, field_matches);
+            let check_constraints =
+                check_constraints(ctx, fields, split_fields, constraints);
+            let check_lengths = check_lengths(ctx, fields, split_fields);
+            let check_required = check_required_fields(ctx, fields, split_fields);
+            let write_fields = fields.iter()
+                .chain(split_fields.iter().flat_map(|split| &split.fields))
+                .map(write_field);
+            quote! {
+                ::syn::MetaItem::List(ref ident, ref values)
+                    if ident.as_ref() == #attribute => {
+                        let value = {
+                            #nest_scope
+                            #(#setup_fields)*
+                            #match_nested
+                            let error_count = errors.len();
+                            #(#check_constraints)*
+                            #(#check_lengths)*
+                            #(#check_required)*
+                            if errors.len() != error_count {
+                                continue;
+                            }
+                            #strukt_ty::#ident {
+                                #(#write_fields)*
+                            }
+                        };
+                        #record
+                    }
+            }
+        }
+    }
+}
+
+/// Generates the implementations, each runs `setup` then its match loop over
+/// the attributes then `finish` to produce the result
+fn impl_from(
+    ctx: &Context,
+    setup: &Tokens,
+    match_loop: Tokens,
+    match_nested: Tokens,
+    finish: &Tokens,
+    captures: &[CaptureField]
+) -> Tokens {
+    let capture_methods = capture_methods(ctx, captures);
+    let Context { strukt_ty,
+                  ref error_ty,
+                  ref warning_ty,
+                  ref life,
+                  ref life_bound,
+                  ref impl_generics,
+                  ref ty_generics,
+                  ref where_clause,
+                  ref trait_path,
+                  ref scope_lit,
+                  .. } = *ctx;
+
+    quote! {
+        impl#impl_generics #trait_path<#life> for #strukt_ty#ty_generics #where_clause {
+            type Error = #error_ty#life_bound;
+            type Warning = #warning_ty#life_bound;
+
+            #[allow(unused_mut, unused_comparisons)]
+            fn try_from_with_warnings(attrs: &#life [::syn::Attribute])
+                -> ::std::result::Result<(Self, ::std::vec::Vec<Self::Warning>), ::std::vec::Vec<Self::Error>>
             {
                 let mut errors = vec![];
-                #(#setup_fields)*
+                let mut warnings: ::std::vec::Vec<Self::Warning> = vec![];
+                let attr_scope: &[&str] = #scope_lit;
+                #setup
                 #match_loop
-                if errors.is_empty() {
-                    Ok(#strukt_ty {
-                        #(#write_fields)*
-                    })
-                } else {
+                #finish
+            }
+
+            #[allow(unused_mut, unused_comparisons)]
+            fn try_from_nested_with_warnings(
+                values: &#life [::syn::NestedMetaItem],
+                attr_scope: &[&#life str])
+                -> ::std::result::Result<(Self, ::std::vec::Vec<Self::Warning>), ::std::vec::Vec<Self::Error>>
+            {
+                let mut errors = vec![];
+                let mut warnings: ::std::vec::Vec<Self::Warning> = vec![];
+                #setup
+                #match_nested
+                #finish
+            }
+
+            #capture_methods
+        }
+
+        impl#impl_generics From<&#life [::syn::Attribute]> for #strukt_ty#ty_generics #where_clause {
+            fn from(attrs: &#life [::syn::Attribute]) -> Self {
+                <Self as #trait_path>::try_from(attrs).unwrap()
+            }
+        }
+    }
+}
+
+pub fn expand(strukt: &Struct, config: &Config) -> Tokens {
+    let mut ctx = Context::new(strukt.ast, strukt.lifetime, config);
+    let inferred = fields_bounds(&ctx, &strukt.fields, &strukt.split_fields);
+    ctx.where_clause = where_clause(&ctx, strukt.ast, &strukt.bound, inferred);
+
+    let setup_fields = strukt.fields
+        .iter()
+        .chain(strukt.split_fields.iter().flat_map(|split| &split.fields))
+        .map(setup_field)
+        .collect::<Vec<_>>();
+    let setup_rest = strukt.rest.as_ref().map(setup_rest);
+    let setup_captures = strukt.captures
+        .iter()
+        .map(|capture| setup_capture(&ctx, capture))
+        .collect::<Vec<_>>();
+    let setup_docs = strukt.docs.as_ref().map(setup_docs);
+    let setup_nested_docs = strukt.docs.as_ref().map(setup_nested_docs);
+    let field_matches = strukt.fields
+        .iter()
+        .map(|field| match_field(&ctx, field))
+        .chain(strukt.split_fields
+            .iter()
+            .map(|split| match_split_fields(&ctx, split)))
+        .collect::<Vec<_>>();
+    let known = known_attributes(&strukt.fields, &strukt.split_fields);
+    let rest = strukt.rest.as_ref();
+    let match_loop = match_loop(&ctx, &known, rest, &strukt.captures, field_matches.iter().cloned());
+    let match_nested = match_nested(&ctx, &known, rest, field_matches.iter().cloned());
+    let write_fields = strukt.fields
+        .iter()
+        .chain(&strukt.docs)
+        .chain(strukt.split_fields.iter().flat_map(|split| &split.fields))
+        .map(write_field)
+        .chain(strukt.rest.as_ref().map(write_rest))
+        .chain(strukt.captures.iter().map(write_capture))
+        .collect::<Vec<_>>();
+    let check_constraints = check_constraints(
+        &ctx, &strukt.fields, &strukt.split_fields, &strukt.constraints);
+    let check_lengths =
+        check_lengths(&ctx, &strukt.fields, &strukt.split_fields);
+    let check_required =
+        check_required_fields(&ctx, &strukt.fields, &strukt.split_fields);
+
+    let validate = validate_item(&ctx, &strukt.validate);
+
+    let strukt_ty = ctx.strukt_ty;
+    let error_enum = error_enum(&ctx);
+    let warning_enum = warning_enum(&ctx);
+    let impl_from = impl_from(
+        &ctx,
+        &quote! {
+            #(#setup_fields)*
+            #setup_rest
+            #(#setup_captures)*
+        },
+        quote! {
+            #setup_docs
+            #match_loop
+        },
+        quote! {
+            #setup_nested_docs
+            #match_nested
+        },
+        &quote! {
+            #(#check_constraints)*
+            #(#check_lengths)*
+            #(#check_required)*
+            if errors.is_empty() {
+                let value = #strukt_ty {
+                    #(#write_fields)*
+                };
+                #validate
+                Ok((value, warnings))
+            } else {
+                Err(errors)
+            }
+        },
+        &strukt.captures);
+
+    quote! {
+        #error_enum
+        #warning_enum
+        #impl_from
+    }
+}
+
+pub fn expand_enum(enoom: &Enum, config: &Config) -> Tokens {
+    let mut ctx = Context::new(enoom.ast, enoom.lifetime, config);
+    let inferred = enoom.variants
+        .iter()
+        .flat_map(|variant| match variant.kind {
+            VariantKind::Word => vec![],
+            VariantKind::NameValue(ref ty) => ty_bounds(&ctx, ty, &[]),
+            VariantKind::List(ref fields, ref split_fields, _) => {
+                fields_bounds(&ctx, fields, split_fields)
+            }
+        })
+        .collect();
+    ctx.where_clause = where_clause(&ctx, enoom.ast, &enoom.bound, inferred);
+
+    let variant_matches = enoom.variants
+        .iter()
+        .map(|variant| match_variant(&ctx, variant))
+        .collect::<Vec<_>>();
+    let known = enoom.variants
+        .iter()
+        .map(|variant| variant.attribute.as_str())
+        .collect::<Vec<_>>();
+    let match_loop = match_loop(&ctx, &known, None, &[], variant_matches.iter().cloned());
+    let match_nested = match_nested(&ctx, &known, None, variant_matches.iter().cloned());
+    let variants = enoom.variants
+        .iter()
+        .map(|variant| &variant.attribute)
+        .collect::<Vec<_>>();
+
+    let validate = validate_item(&ctx, &enoom.validate);
+
+    let error_ty = &ctx.error_ty;
+    let impl_from = impl_from(
+        &ctx,
+        &quote! {
+            let mut found = None;
+        },
+        match_loop,
+        match_nested,
+        &quote! {
+            match found {
+                Some((_, value)) => {
+                    if errors.is_empty() {
+                        #validate
+                        Ok((value, warnings))
+                    } else {
+                        Err(errors)
+                    }
+                }
+                None => {
+                    errors.push(#error_ty::MissingVariant {
+                        scope: attr_scope.to_vec(),
+                        variants: &[#(#variants),*],
+                    });
                     Err(errors)
                 }
             }
-        }
+        },
+        &[]);
+    let error_enum = error_enum(&ctx);
+    let warning_enum = warning_enum(&ctx);
+
+    quote! {
+        #error_enum
+        #warning_enum
+        #impl_from
+    }
+}
+
+pub fn expand_value(value_enum: &ValueEnum, config: &Config) -> Tokens {
+    let krate = syn::Ident::new(config.krate);
+    let ident = &value_enum.ast.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        value_enum.ast.generics.split_for_impl();
+
+    let arms = value_enum.variants
+        .iter()
+        .map(|variant| {
+            let variant_ident = variant.ident;
+            let names = Some(&variant.name).into_iter().chain(&variant.aliases);
+            quote! {
+                #(#names)|* => Ok(#ident::#variant_ident),
+            }
+        })
+        .collect::<Vec<_>>();
+    let choices = value_enum.variants
+        .iter()
+        .map(|variant| &variant.name)
+        .collect::<Vec<_>>();
 
-        impl<#life> From<&#life [::syn::Attribute]> for #strukt_ty#opt_life_bound {
-            fn from(attrs: &[::syn::Attribute]) -> #strukt_ty {
-                #strukt_ty::try_from(attrs).unwrap()
+    quote! {
+        impl#impl_generics ::std::str::FromStr for #ident#ty_generics #where_clause {
+            type Err = ::#krate::InvalidChoice;
+
+            fn from_str(value: &str) -> ::std::result::Result<Self, Self::Err> {
+                match value {
+                    #(#arms)*
+                    _ => Err(::#krate::InvalidChoice::new(value, &[#(#choices),*])),
+                }
             }
         }
     }
@@ -548,7 +2371,10 @@ impl<'a> ToTokens for Ty<'a> {
             Ty::Literal(Lit::Str) => tokens.append("str"),
             Ty::Literal(Lit::ByteStr) => tokens.append("bytestr"),
             Ty::Literal(Lit::Float(ty)) => tokens.append(&ty.to_string()),
-            Ty::Custom(ty) => ty.to_tokens(tokens),
+            Ty::Custom(ty)
+            | Ty::Nested(ty)
+            | Ty::Syn(_, ty)
+            | Ty::Borrowed(_, ty) => ty.to_tokens(tokens),
         }
     }
 }
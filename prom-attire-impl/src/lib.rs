@@ -1,25 +1,57 @@
-#![recursion_limit = "128"]
+#![recursion_limit = "256"]
 #![allow(unknown_lints)] // For clippy
 
 #[macro_use]
 extern crate error_chain;
+extern crate proc_macro;
 extern crate syn;
 #[macro_use]
 extern crate quote;
 
+mod diagnostic;
 mod dissect;
 mod expand;
 mod errors;
+#[doc(hidden)]
+pub mod runtime;
 mod tmp;
 
+use std::borrow::Cow;
 use std::str::FromStr;
+pub use diagnostic::{Location, compile_errors};
 pub use errors::*;
+pub use runtime::{FromAttributes, Warning, InvalidChoice};
 use tmp::TryInto;
 
 pub struct Config<'a> {
+    pub krate: &'a str,
     pub scope: Option<&'a str>,
+    /// Other scopes accepted in place of `scope`, such as an old name that is
+    /// being migrated away from, ignored without a `scope`
+    pub scope_aliases: &'a [&'a str],
+    /// Other scopes accepted in place of `scope` that produce a warning when
+    /// used, ignored without a `scope`
+    pub deprecated_scopes: &'a [&'a str],
     pub docs: Option<&'a str>,
-    pub parse_field_config: &'a Fn(&[syn::Attribute]) -> FieldConfig,
+    pub allow_unknown: bool,
+    /// A field collecting the attributes that no other field matches
+    pub rest: Option<&'a str>,
+    /// Groups of comma separated field names, at most one field of each
+    /// group may be specified
+    pub exclusive: &'a [&'a str],
+    /// Groups of comma separated field names, at least one field of each
+    /// group must be specified
+    pub at_least_one: &'a [&'a str],
+    /// A function checking the finished struct or enum
+    pub validate: Option<&'a str>,
+    /// A case convention applied to the attribute names taken from field and
+    /// variant names
+    pub rename_all: Option<RenameRule>,
+    /// Where predicates replacing the bounds inferred for type parameters
+    pub bound: Option<&'a str>,
+    #[allow(type_complexity)]
+    pub parse_field_config: &'a Fn(&[syn::Attribute])
+        -> std::result::Result<FieldConfig, Vec<String>>,
 }
 
 #[derive(Debug)]
@@ -28,6 +60,34 @@ pub struct FieldConfig<'a> {
     pub split_attribute_of: Option<&'a str>,
     pub default: Defaulted,
     pub flag_value: Option<&'a str>,
+    pub nested: bool,
+    pub capture: Option<Capture>,
+    pub duplicate: DuplicatePolicy,
+    pub required: bool,
+    pub separator: Option<&'a str>,
+    /// Other attribute names accepted for the field
+    pub aliases: Vec<&'a str>,
+    /// Old attribute names still accepted for the field, producing a warning
+    /// when used
+    pub deprecated_aliases: Vec<&'a str>,
+    /// A note explaining what to use instead, producing a warning when the
+    /// field's attribute is used
+    pub deprecated: Option<&'a str>,
+    /// Fields that may not be specified along with this one
+    pub conflicts_with: Vec<&'a str>,
+    /// Fields that must be specified if this one is
+    pub requires: Vec<&'a str>,
+    /// A function checking each value parsed for the field
+    pub validate: Option<&'a str>,
+    /// Bounds on the values of an integer or float field
+    pub min: Option<Cow<'a, str>>,
+    pub max: Option<Cow<'a, str>>,
+    /// Bounds on the number of characters in a string field, or the number
+    /// of elements in a collection field
+    pub min_len: Option<usize>,
+    pub max_len: Option<usize>,
+    /// Shorthand for a `min_len` of 1
+    pub non_empty: bool,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -47,17 +107,180 @@ impl Default for FieldConfig<'static> {
             split_attribute_of: None,
             default: Defaulted::Nope,
             flag_value: None,
+            nested: false,
+            capture: None,
+            duplicate: DuplicatePolicy::Error,
+            required: false,
+            separator: None,
+            aliases: Vec::new(),
+            deprecated_aliases: Vec::new(),
+            deprecated: None,
+            conflicts_with: Vec::new(),
+            requires: Vec::new(),
+            validate: None,
+            min: None,
+            max: None,
+            min_len: None,
+            max_len: None,
+            non_empty: false,
         }
     }
 }
 
 pub fn derive(input: &str, config: &Config) -> Result<String> {
     let ast = syn::parse_derive_input(input)?;
-    let strukt = (&ast, config).try_into()?;
-    let expanded = expand::expand(&strukt, config);
+    let expanded = match ast.body {
+        syn::Body::Struct(_) => {
+            let strukt = (&ast, config).try_into()?;
+            expand::expand(&strukt, config)
+        }
+        syn::Body::Enum(_) => {
+            let enoom = (&ast, config).try_into()?;
+            expand::expand_enum(&enoom, config)
+        }
+    };
     Ok(expanded.to_string())
 }
 
+/// Implements `FromStr` for a fieldless enum, the variants are matched by
+/// their name in kebab-case, or snake_case so that they can be given as a
+/// word, unless renamed via the `attribute` field config
+pub fn derive_value(input: &str, config: &Config) -> Result<String> {
+    let ast = syn::parse_derive_input(input)?;
+    let value_enum = (&ast, config).try_into()?;
+    Ok(expand::expand_value(&value_enum, config).to_string())
+}
+
+/// A detail of the annotated item to store in a field instead of parsing an
+/// attribute into it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Capture {
+    /// The `syn::Ident` of the item
+    Ident,
+    /// The `syn::Visibility` of the item
+    Vis,
+    /// The `syn::Ty` of a field
+    Ty,
+    /// The `syn::Generics` of a struct or enum
+    Generics,
+    /// Which of the scope or its aliases the attributes were found in
+    Scope,
+}
+
+impl FromStr for Capture {
+    type Err = UnknownValue;
+
+    fn from_str(string: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match string {
+            "ident" => Capture::Ident,
+            "vis" => Capture::Vis,
+            "ty" => Capture::Ty,
+            "generics" => Capture::Generics,
+            "scope" => Capture::Scope,
+            _ => {
+                return Err(UnknownValue(
+                    "`ident`, `vis`, `ty`, `generics` or `scope`"));
+            }
+        })
+    }
+}
+
+/// What to do when a non-collection field's attribute, or a map key, is
+/// specified more than once
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DuplicatePolicy {
+    /// Return a `Duplicate` error
+    Error,
+    /// Keep the first value specified
+    First,
+    /// Keep the last value specified
+    Last,
+}
+
+impl FromStr for DuplicatePolicy {
+    type Err = UnknownValue;
+
+    fn from_str(string: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match string {
+            "error" => DuplicatePolicy::Error,
+            "first" => DuplicatePolicy::First,
+            "last" => DuplicatePolicy::Last,
+            _ => return Err(UnknownValue("`error`, `first` or `last`")),
+        })
+    }
+}
+
+/// A case convention for the attribute names derived from `snake_case` field
+/// names and variant names
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RenameRule {
+    /// `kebab-case`, attribute names keep their `snake_case` form as syn
+    /// can't parse names containing `-`
+    KebabCase,
+    /// `camelCase`
+    CamelCase,
+    /// `PascalCase`
+    PascalCase,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnakeCase,
+}
+
+impl RenameRule {
+    /// Converts a `snake_case` name to this convention
+    pub fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::KebabCase => name.replace('_', "-"),
+            RenameRule::ScreamingSnakeCase => name.to_uppercase(),
+            RenameRule::CamelCase | RenameRule::PascalCase => {
+                let mut renamed = String::with_capacity(name.len());
+                let mut upper = self == RenameRule::PascalCase;
+                for c in name.chars() {
+                    if c == '_' {
+                        upper = !renamed.is_empty();
+                    } else if upper {
+                        renamed.extend(c.to_uppercase());
+                        upper = false;
+                    } else {
+                        renamed.push(c);
+                    }
+                }
+                renamed
+            }
+        }
+    }
+}
+
+impl FromStr for RenameRule {
+    type Err = UnknownValue;
+
+    fn from_str(string: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match string {
+            "kebab-case" => RenameRule::KebabCase,
+            "camelCase" => RenameRule::CamelCase,
+            "PascalCase" => RenameRule::PascalCase,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnakeCase,
+            _ => {
+                return Err(UnknownValue(
+                    "`kebab-case`, `camelCase`, `PascalCase` or `SCREAMING_SNAKE_CASE`"));
+            }
+        })
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct UnknownValue(&'static str);
+impl ::std::fmt::Display for UnknownValue {
+    fn fmt(&self, w: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(w, "expected one of {}", self.0)
+    }
+}
+impl ::std::error::Error for UnknownValue {
+    fn description(&self) -> &str {
+        "unknown value"
+    }
+}
+
 #[doc(hidden)]
 pub struct NoError;
 impl FromStr for Defaulted {
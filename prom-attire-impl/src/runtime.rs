@@ -0,0 +1,210 @@
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+
+use syn::{self, Attribute, DeriveInput, Field, NestedMetaItem, Variant,
+          WherePredicate};
+
+/// A type that can be parsed from the attributes on an item, implemented by
+/// `#[derive(PromAttire)]`.
+pub trait FromAttributes<'a>: Sized {
+    /// The error type produced when some attributes fail to parse.
+    type Error: Error + 'a;
+
+    /// The warning type produced for attributes that were parsed but should
+    /// be changed, such as deprecated names.
+    type Warning: Warning + 'a;
+
+    /// Parse from the attributes on an item, returning every error encountered
+    /// if any attribute could not be parsed.
+    fn try_from(attrs: &'a [Attribute]) -> Result<Self, Vec<Self::Error>> {
+        Self::try_from_with_warnings(attrs).map(|(value, _)| value)
+    }
+
+    /// Parse from the values nested inside a list attribute, `scope` is the
+    /// path of list attributes that contain these values and is used when
+    /// reporting errors.
+    fn try_from_nested(values: &'a [NestedMetaItem], scope: &[&'a str])
+        -> Result<Self, Vec<Self::Error>> {
+        Self::try_from_nested_with_warnings(values, scope)
+            .map(|(value, _)| value)
+    }
+
+    /// As `try_from`, but also returning any warnings encountered while
+    /// parsing.
+    #[allow(type_complexity)]
+    fn try_from_with_warnings(attrs: &'a [Attribute])
+        -> Result<(Self, Vec<Self::Warning>), Vec<Self::Error>>;
+
+    /// As `try_from_nested`, but also returning any warnings encountered
+    /// while parsing.
+    #[allow(type_complexity)]
+    fn try_from_nested_with_warnings(
+        values: &'a [NestedMetaItem],
+        scope: &[&'a str]
+    ) -> Result<(Self, Vec<Self::Warning>), Vec<Self::Error>>;
+
+    /// Parse from the attributes on a struct or enum, storing its ident,
+    /// visibility and generics in any fields that capture them.
+    fn from_derive_input(input: &'a DeriveInput)
+        -> Result<Self, Vec<Self::Error>> {
+        Self::try_from(&input.attrs)
+    }
+
+    /// Parse from the attributes on a field, storing its ident, visibility
+    /// and type in any fields that capture them.
+    fn from_field(field: &'a Field) -> Result<Self, Vec<Self::Error>> {
+        Self::try_from(&field.attrs)
+    }
+
+    /// Parse from the attributes on an enum variant, storing its ident in any
+    /// field that captures it.
+    fn from_variant(variant: &'a Variant) -> Result<Self, Vec<Self::Error>> {
+        Self::try_from(&variant.attrs)
+    }
+}
+
+/// A problem with some attributes that doesn't prevent them being parsed,
+/// implemented for every type that can be displayed and debugged.
+pub trait Warning: fmt::Display + fmt::Debug {}
+
+impl<T: fmt::Display + fmt::Debug + ?Sized> Warning for T {}
+
+/// The error returned when parsing an `#[derive(AttireValue)]` enum from a
+/// value that doesn't name any of its variants.
+#[derive(Debug)]
+pub struct InvalidChoice {
+    value: String,
+    choices: &'static [&'static str],
+}
+
+impl InvalidChoice {
+    pub fn new(value: &str, choices: &'static [&'static str]) -> InvalidChoice {
+        InvalidChoice {
+            value: value.to_owned(),
+            choices: choices,
+        }
+    }
+
+    /// The value that failed to parse.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The names of every variant, not including aliases.
+    pub fn choices(&self) -> &'static [&'static str] {
+        self.choices
+    }
+}
+
+impl fmt::Display for InvalidChoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown value `{}`, expected one of {}",
+            self.value,
+            self.choices
+                .iter()
+                .map(|choice| format!("`{}`", choice))
+                .collect::<Vec<_>>()
+                .join(", "))
+    }
+}
+
+impl Error for InvalidChoice {
+    fn description(&self) -> &str {
+        "unknown value"
+    }
+}
+
+/// The error `syn` reports when an attribute value fails to parse into one
+/// of its types.
+#[derive(Debug)]
+pub struct SynError(String);
+
+impl SynError {
+    pub fn new(message: String) -> SynError {
+        SynError(message)
+    }
+}
+
+impl fmt::Display for SynError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for SynError {
+    fn description(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Parses a single where predicate such as `T: Clone`, `syn` only exposes a
+/// parser for entire where clauses.
+pub fn parse_where_predicate(input: &str) -> Result<WherePredicate, String> {
+    let mut clause = syn::parse_where_clause(&format!("where {}", input))?;
+    if clause.predicates.len() != 1 {
+        return Err(format!(
+            "expected a single where predicate but found {}",
+            clause.predicates.len()));
+    }
+    Ok(clause.predicates.remove(0))
+}
+
+/// Splits `value` on `separator`, trimming whitespace from each element and
+/// skipping any that are empty. A separator of only whitespace splits on any
+/// run of whitespace.
+pub fn split<'a>(value: &'a str, separator: &str) -> Vec<&'a str> {
+    if separator.trim().is_empty() {
+        return value.split_whitespace().collect();
+    }
+    value.split(separator)
+        .map(str::trim)
+        .filter(|element| !element.is_empty())
+        .collect()
+}
+
+/// Finds the known attribute name closest to `attr`, as long as it is close
+/// enough that `attr` is likely a misspelling of it.
+pub fn suggest(attr: &str, known: &[&'static str]) -> Option<&'static str> {
+    let len = attr.chars().count();
+    // Short names are allowed a single edit, but shouldn't be replaced
+    // entirely
+    let max_distance = cmp::min(cmp::max(len, 3) / 3, len.saturating_sub(1));
+    known.iter()
+        .map(|&name| (edit_distance(attr, name), name))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, name)| name)
+}
+
+/// The Levenshtein distance between `a` and `b`, counting transpositions of
+/// adjacent characters as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..a.len() + 1 {
+        for j in 1..b.len() + 1 {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = cmp::min(
+                cmp::min(distances[i - 1][j] + 1, distances[i][j - 1] + 1),
+                distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = cmp::min(distance, distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
@@ -4,7 +4,7 @@ extern crate prom_attire_impl;
 extern crate quote;
 extern crate syn;
 
-use prom_attire_impl::{Config, FieldConfig, ErrorKind};
+use prom_attire_impl::{Config, FieldConfig, Capture, Defaulted, ErrorKind, Location};
 
 macro_rules! assert_error_kind {
     ($err:expr, $kind:pat) => {{
@@ -21,26 +21,46 @@ macro_rules! assert_error_kind {
     }}
 }
 
+/// The config for an unscoped struct with no options, each test overrides
+/// the fields it needs
+fn config() -> Config<'static> {
+    Config {
+        krate: "prom_attire",
+        scope: None,
+        scope_aliases: &[],
+        deprecated_scopes: &[],
+        docs: None,
+        allow_unknown: false,
+        rest: None,
+        exclusive: &[],
+        at_least_one: &[],
+        validate: None,
+        rename_all: None,
+        bound: None,
+        parse_field_config: &|_| Ok(FieldConfig::default()),
+    }
+}
+
 #[test]
 fn enuum() {
     let input = quote! { enum A {} };
-    let config = Config {
-        scope: None,
-        docs: None,
-        parse_field_config: &|_| FieldConfig::default(),
-    };
+    let config = config();
     let result = prom_attire_impl::derive(input.as_str(), &config);
-    assert_error_kind!(result.unwrap_err(), ErrorKind::StructBody)
+    assert_error_kind!(result.unwrap_err(), ErrorKind::EnumBody)
+}
+
+#[test]
+fn tuple_variant() {
+    let input = quote! { enum A { B(u8, u8) } };
+    let config = config();
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    assert_error_kind!(result.unwrap_err(), ErrorKind::Variant(_))
 }
 
 #[test]
 fn tuple_struct() {
     let input = quote! { struct A(); };
-    let config = Config {
-        scope: None,
-        docs: None,
-        parse_field_config: &|_| FieldConfig::default(),
-    };
+    let config = config();
     let result = prom_attire_impl::derive(input.as_str(), &config);
     assert_error_kind!(result.unwrap_err(), ErrorKind::StructBody)
 }
@@ -53,10 +73,486 @@ fn bad_docs_type() {
         }
     };
     let config = Config {
-        scope: None,
         docs: Some("docs"),
-        parse_field_config: &|_| FieldConfig::default(),
+        ..config()
     };
     let result = prom_attire_impl::derive(input.as_str(), &config);
     assert_error_kind!(result.unwrap_err(), ErrorKind::DocsTy(_))
 }
+
+#[test]
+fn bad_nested_type() {
+    let input = quote! {
+        struct A {
+            b: Option<u64>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            nested: true,
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    assert_error_kind!(result.unwrap_err(), ErrorKind::Field(_))
+}
+
+#[test]
+fn bad_field_attributes() {
+    let input = quote! {
+        struct A {
+            b: Option<u8>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Err(vec!["foo".to_owned(), "bar".to_owned()]),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_error_kind!(&err, ErrorKind::Field(_));
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: foo".to_owned()),
+        (Location::Member(vec!["b"]), "field `b` had an error: bar".to_owned()),
+    ]);
+}
+
+#[test]
+fn variant_field_diagnostic() {
+    let input = quote! {
+        enum A {
+            B,
+            C { d: u8 },
+        }
+    };
+    let config = config();
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["C", "d"]), "variant `C` had an error: field `d` had an error: unwrapped type `u8` without a default is not supported, it must be enclosed in a `Vec` or `Option` or have a `default` specified".to_owned()),
+    ]);
+}
+
+#[test]
+fn variant_options() {
+    let input = quote! {
+        enum A {
+            B,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            required: true,
+            flag_value: Some("true"),
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["B"]), "variant `B` had an error: `flag_value`, `required` cannot be used on a variant, only `attribute`".to_owned()),
+    ]);
+}
+
+#[test]
+fn item_diagnostic() {
+    let input = quote! { struct A(); };
+    let config = config();
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Item, "can only be applied to normal structs".to_owned()),
+    ]);
+}
+
+#[test]
+fn capture_not_option() {
+    let input = quote! {
+        struct A<'a> {
+            b: &'a syn::Ident,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            capture: Some(Capture::Ident),
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    assert_error_kind!(result.unwrap_err(), ErrorKind::Field(_))
+}
+
+#[test]
+fn capture_in_variant() {
+    let input = quote! {
+        enum A<'a> {
+            B {
+                #[attire(capture = "ident")]
+                c: Option<&'a syn::Ident>,
+            },
+        }
+    };
+    let config = Config {
+        parse_field_config: &|attrs| Ok(if attrs.is_empty() {
+            FieldConfig::default()
+        } else {
+            FieldConfig {
+                capture: Some(Capture::Ident),
+                ..FieldConfig::default()
+            }
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_error_kind!(&err, ErrorKind::Variant(_));
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["B", "c"]), "variant `B` had an error: field `c` had an error: captures are only supported on struct fields".to_owned()),
+    ]);
+}
+
+#[test]
+fn required_option() {
+    let input = quote! {
+        struct A {
+            b: Option<u8>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            required: true,
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    assert_error_kind!(result.unwrap_err(), ErrorKind::Field(_))
+}
+
+#[test]
+fn required_with_default() {
+    let input = quote! {
+        struct A {
+            b: u8,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            required: true,
+            default: Defaulted::Yep,
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: a field cannot be both `required` and have a `default`".to_owned()),
+    ]);
+}
+
+#[test]
+fn separator_not_collection() {
+    let input = quote! {
+        struct A {
+            b: Option<u8>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            separator: Some(","),
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: field type `Option < u8 >` with a `separator` must be a `Vec`, `HashSet` or `BTreeSet` of non-nested values other than `syn::Lit`".to_owned()),
+    ]);
+}
+
+#[test]
+fn separator_lit() {
+    let input = quote! {
+        struct A {
+            b: Vec<syn::Lit>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            separator: Some(","),
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: field type `Vec < syn :: Lit >` with a `separator` must be a `Vec`, `HashSet` or `BTreeSet` of non-nested values other than `syn::Lit`".to_owned()),
+    ]);
+}
+
+#[test]
+fn separator_borrowed_lit() {
+    let input = quote! {
+        struct A<'a> {
+            b: Vec<&'a syn::Lit>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            separator: Some(","),
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: field type `Vec < & 'a syn :: Lit >` with a `separator` must be a `Vec`, `HashSet` or `BTreeSet` of non-nested values other than `syn::Lit`".to_owned()),
+    ]);
+}
+
+#[test]
+fn split_map() {
+    let input = quote! {
+        struct A {
+            b: HashMap<String, u8>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            split_attribute_of: Some("c"),
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: map fields cannot have a `flag_value` or be split from another attribute".to_owned()),
+    ]);
+}
+
+#[test]
+fn split_meta_item() {
+    let input = quote! {
+        struct A<'a> {
+            b: Option<&'a syn::MetaItem>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            split_attribute_of: Some("c"),
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: field type `Option < & 'a syn :: MetaItem >` borrows a raw attribute, it must be a reference, an `Option` or a `Vec` and cannot have a `flag_value`, `separator` or be split from another attribute".to_owned()),
+    ]);
+}
+
+#[test]
+fn rest_ty() {
+    let input = quote! {
+        struct A {
+            b: Vec<String>,
+        }
+    };
+    let config = Config {
+        rest: Some("b"),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "rest field `b` must be a Vec<&syn::MetaItem> or Vec<syn::MetaItem>".to_owned()),
+    ]);
+}
+
+#[test]
+fn unknown_constraint_field() {
+    let input = quote! {
+        struct A {
+            b: Option<u8>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            requires: vec!["c"],
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: constraint refers to unknown field `c`".to_owned()),
+    ]);
+}
+
+#[test]
+fn bad_bound() {
+    let input = quote! {
+        struct A<T> {
+            b: Option<T>,
+        }
+    };
+    let config = Config {
+        bound: Some("T:: Clone"),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    assert_error_kind!(result.unwrap_err(), ErrorKind::Bound(_, _))
+}
+
+#[test]
+fn bad_validate() {
+    let input = quote! {
+        struct A {
+            b: Option<u8>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            validate: Some("not a path"),
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    let diagnostics = err.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].0, Location::Member(vec!["b"]));
+    assert!(diagnostics[0].1.starts_with("field `b` had an error: validate function `not a path` could not be parsed as a path"),
+            "{}", diagnostics[0].1);
+}
+
+#[test]
+fn range_not_number() {
+    let input = quote! {
+        struct A<'a> {
+            b: Option<&'a str>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            min: Some("1".into()),
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: field type `Option < & 'a str >` with a `min` or `max` must contain integer or float values".to_owned()),
+    ]);
+}
+
+#[test]
+fn range_value() {
+    let input = quote! {
+        struct A {
+            b: Option<u8>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            max: Some("256".into()),
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: bound `256` is not a valid value of field type `Option < u8 >`".to_owned()),
+    ]);
+}
+
+#[test]
+fn length_not_string() {
+    let input = quote! {
+        struct A {
+            b: Option<u8>,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            non_empty: true,
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["b"]), "field `b` had an error: field type `Option < u8 >` with a `min_len`, `max_len` or `non_empty` must be a string or a `Vec`, `HashSet`, `BTreeSet`, `HashMap` or `BTreeMap`".to_owned()),
+    ]);
+}
+
+#[test]
+fn value_variant_fields() {
+    let input = quote! {
+        enum A {
+            B,
+            C(u8),
+        }
+    };
+    let config = config();
+    let result = prom_attire_impl::derive_value(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["C"]), "variant `C` had an error: variants must be unit variants".to_owned()),
+    ]);
+}
+
+#[test]
+fn value_variant_options() {
+    let input = quote! {
+        enum A {
+            B,
+            C,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            aliases: vec!["d"],
+            default: Defaulted::Yep,
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive_value(input.as_str(), &config);
+    let err = result.unwrap_err();
+    assert_eq!(err.diagnostics(), vec![
+        (Location::Member(vec!["B"]), "variant `B` had an error: `default` cannot be used on a variant, only `attribute` and `alias`".to_owned()),
+    ]);
+}
+
+#[test]
+fn value_duplicate_name() {
+    let input = quote! {
+        enum A {
+            Fast,
+            Quick,
+        }
+    };
+    let config = Config {
+        parse_field_config: &|_| Ok(FieldConfig {
+            aliases: vec!["speedy"],
+            ..FieldConfig::default()
+        }),
+        ..config()
+    };
+    let result = prom_attire_impl::derive_value(input.as_str(), &config);
+    assert_error_kind!(result.unwrap_err(), ErrorKind::ValueName(_))
+}
@@ -24,6 +24,8 @@
 //! # impl From<String> for E { fn from(_: String) -> E { E } }
 //! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
 //! # fn foo() -> Result<(), E> {
+//! use prom_attire::FromAttributes;
+//!
 //! #[derive(PromAttire, PartialEq, Debug)]
 //! struct Attributes {
 //!     awesome: Option<String>,
@@ -58,6 +60,7 @@
 //! # impl From<String> for E { fn from(_: String) -> E { E } }
 //! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
 //! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
 //! use std::net::{IpAddr, Ipv4Addr};
 //! #[derive(PromAttire, PartialEq, Debug)]
 //! struct Attributes {
@@ -76,14 +79,85 @@
 //! # foo().unwrap()
 //! # }
 //! ```
+//!
+//! Integer, float and boolean literals are also accepted for these types,
+//! they are converted back to their textual form before being parsed, so
+//! `#[timeout = 30]` works just as well as `#[timeout = "30"]`.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! use std::num::NonZeroU32;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! struct Attributes {
+//!     timeout: Option<NonZeroU32>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[timeout = 30]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Attributes {
+//!     timeout: NonZeroU32::new(30),
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
+//!
+//! A few `syn` types are also supported, parsed from the contents of a string
+//! literal: `syn::Path`, `syn::Ty`, `syn::Ident`, `syn::Expr` (which needs
+//! `syn`'s `full` feature) and `syn::WherePredicate`. These must be written
+//! with the `syn::` prefix to be recognised. A `syn::Lit` field takes the
+//! attribute's literal as is, whatever kind it is.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "serde")]
+//! struct Attributes {
+//!     with: Option<syn::Path>,
+//!     bound: Option<syn::WherePredicate>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[serde(with = \"my::module\", bound = \"T: Clone\")]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Attributes {
+//!     with: Some(syn::parse_path("my::module")?),
+//!     bound: syn::parse_where_clause("where T: Clone")?.predicates.pop(),
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
 //! ## Errors
 //!
-//! There are two methods added to your attribute type, one is an
-//! implementation of `From<&[syn::Attribute]>` that will panic if any error
-//! occurs. The other has the signature `fn try_from(attrs: &[syn::Attribute])
-//! -> Result<A, Vec<E>>` where `A` is your attribute type and `E` is a
-//! generated error type named `YourStructName + FromAttributesError`.
+//! There are two traits implemented for your attribute type, one is
+//! `From<&[syn::Attribute]>` which will panic if any error occurs. The other is
+//! [`FromAttributes`](trait.FromAttributes.html), which provides `fn
+//! try_from(attrs: &[syn::Attribute]) -> Result<A, Vec<A::Error>>` where `A`
+//! is your attribute type and `A::Error` is a generated error type named
+//! `YourStructName + FromAttributesError`.
 //!
 //! The most common error will be that parsing the value provided by your users
 //! failed, inspecting that error could look something like
@@ -97,6 +171,7 @@
 //! # impl From<String> for E { fn from(_: String) -> E { E } }
 //! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
 //! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
 //! use std::net::{IpAddr, Ipv4Addr};
 //! #[derive(PromAttire, PartialEq, Debug)]
 //! struct Attributes {
@@ -107,7 +182,7 @@
 //!     struct Foo {}
 //! ")?;
 //! let errs = Attributes::try_from(ast.attrs.as_slice()).unwrap_err();
-//! if let AttributesFromAttributesError::Parsing { value, attr, ref err, .. } = errs[0] {
+//! if let AttributesFromAttributesError::Parsing { ref value, attr, ref err, .. } = errs[0] {
 //!     assert_eq!(value, "31.220.0");
 //!     assert_eq!(attr, "boom");
 //!     // assert_eq!(err, Box::new("31.220.0".parse::<IpAddr>().unwrap_err()));
@@ -122,15 +197,25 @@
 //! implementation, it is recommended that you treat them as opaque errors as
 //! much as possible and open bug tickets if there are some enhancements that
 //! you feel would make the resulting messages nicer for your users. However
-//! they are (at least currently) exposed to the module in which your attribute
-//! struct is defined if you need to pull details from them.
+//! they are (at least currently) exposed with the same visibility as your
+//! attribute struct if you need to pull details from them.
+//!
+//! Some problems don't stop the attributes being parsed, such as deprecated
+//! names, unknown attributes in an `allow_unknown` scope, or duplicates kept
+//! by a `first` or `last` duplicate policy. These are reported as warnings
+//! by `try_from_with_warnings`, which returns the parsed value along with a
+//! `Vec` of the generated `YourStructName + FromAttributesWarning` type for
+//! you to surface however suits your macro.
 
 //! ## Lifetimes
 //!
 //! So far these examples have shown only examples of owned types. You can also
-//! use types that borrow from the provided AST by specifying a single lifetime
-//! on the struct. (Currently this only supports `&str` as `FromStr` does not
-//! support borrowing from the input, this may be extended in the future).
+//! use types that borrow from the provided AST by specifying a lifetime on the
+//! struct. As `FromStr` does not support borrowing from the input only a few
+//! types can be borrowed: `&str`, `&[u8]`, `Cow<str>` (which is owned when the
+//! value is a non-string literal) and the raw `&syn::Lit`. The first lifetime
+//! is the one borrowed from the attributes, any extra lifetimes are carried
+//! through and must be outlived by it.
 //!
 //! ```rust
 //! # #[macro_use] extern crate prom_attire;
@@ -141,6 +226,7 @@
 //! # impl From<String> for E { fn from(_: String) -> E { E } }
 //! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
 //! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
 //! #[derive(PromAttire, PartialEq, Debug)]
 //! struct Attributes<'a> {
 //!     awesome: Option<&'a str>,
@@ -158,6 +244,74 @@
 //! # foo().unwrap()
 //! # }
 //! ```
+//!
+//! For attributes that need to be interpreted later, a field of type
+//! `&syn::MetaItem` takes the whole matching item, and `&[syn::NestedMetaItem]`
+//! takes the items of a list attribute.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! struct Attributes<'a> {
+//!     serde: Option<&'a [syn::NestedMetaItem]>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[serde(skip)]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs.serde.map(|items| items.len()), Some(1));
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
+
+//! ## Generics
+//!
+//! Attribute structs can take type parameters, allowing a reusable struct to
+//! be instantiated with different value types. Bounds are inferred for every
+//! field type using a type parameter: `FromStr` (with an `Error` as its
+//! `Err`) for parsed values, `FromAttributes` for nested ones, plus `Default`,
+//! `Clone`, `Hash + Eq` or `Ord` where the field needs them. If the inferred
+//! bounds aren't right they can be replaced entirely with `#[attire(bound =
+//! "T: MyTrait")]`.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! struct Rename<T> {
+//!     rename: Option<T>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[rename = \"5\"]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Rename::<u32>::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Rename {
+//!     rename: Some(5),
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
 //! ## Multiple Values
 //!
@@ -174,6 +328,7 @@
 //! # impl From<String> for E { fn from(_: String) -> E { E } }
 //! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
 //! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
 //! #[derive(PromAttire, PartialEq, Debug)]
 //! struct Attributes<'a> {
 //!     awesome: Vec<&'a str>,
@@ -192,6 +347,112 @@
 //! # foo().unwrap()
 //! # }
 //! ```
+//!
+//! The values may also be given as a list of words or literals, such as
+//! `#[derives(Debug, Clone)]`, each of which is parsed into the element type.
+//! `HashSet` and `BTreeSet` can be used in place of `Vec` to ignore repeated
+//! values.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! use std::collections::BTreeSet;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! struct Attributes<'a> {
+//!     derives: BTreeSet<&'a str>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[derives(Debug, Clone)]
+//!     #[derives = \"Debug\"]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Attributes {
+//!     derives: vec!["Clone", "Debug"].into_iter().collect(),
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
+//!
+//! A single string can be split into multiple values too by giving the field
+//! a separator with `#[attire(separator = ",")]`. Each element is trimmed and
+//! parsed separately, any `Parsing` error includes the `index` of the
+//! element that failed. A separator of `" "` splits on any whitespace.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! struct Attributes<'a> {
+//!     #[attire(separator = ",")]
+//!     features: Vec<&'a str>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[features = \"a, b, c\"]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Attributes {
+//!     features: vec!["a", "b", "c"],
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
+
+//! ## Maps
+//!
+//! A `HashMap` or `BTreeMap` field is filled from the `key = value` items
+//! nested inside its attribute, e.g. `#[env(KEY = "value", OTHER = "x")]`.
+//! Keys are parsed from the item names, so they are usually `&str` or
+//! `String`, and values are parsed in the same way as any other attribute.
+//! Specifying a key more than once returns a `DuplicateKey` error unless the
+//! field has a `duplicate` policy set.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! use std::collections::BTreeMap;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! struct Attributes<'a> {
+//!     env: BTreeMap<&'a str, &'a str>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[env(KEY = \"value\", OTHER = \"x\")]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs.env["KEY"], "value");
+//! assert_eq!(attrs.env["OTHER"], "x");
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
 //! ## Scoping
 //!
@@ -209,6 +470,7 @@
 //! # impl From<String> for E { fn from(_: String) -> E { E } }
 //! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
 //! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
 //! #[derive(PromAttire, PartialEq, Debug)]
 //! #[attire(scope = "you")]
 //! struct Attributes<'a> {
@@ -227,6 +489,89 @@
 //! # foo().unwrap()
 //! # }
 //! ```
+//!
+//! Any attribute within the namespace that doesn't correspond to a field is
+//! returned as an `UnknownAttribute` error, along with a suggestion of the
+//! closest known attribute if it looks like a misspelling. If the namespace
+//! is shared with another macro you can add `#[attire(allow_unknown)]` to the
+//! struct to only report them as warnings instead.
+//!
+//! To handle the unknown attributes yourself, such as forwarding them to
+//! another macro, name a `Vec<&syn::MetaItem>` (or owned `Vec<syn::MetaItem>`)
+//! field with `#[attire(rest = "field")]` and they will be collected into it.
+//! Without a scope every other attribute on the item is collected.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "you", rest = "extra")]
+//! struct Attributes<'a> {
+//!     awesome: Option<&'a str>,
+//!     extra: Vec<&'a syn::MetaItem>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[you(awesome = \"yes\", cool)]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Attributes {
+//!     awesome: Some("yes"),
+//!     extra: vec![&syn::MetaItem::Word("cool".into())],
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
+//!
+//! The `scope` can be given more than once to accept several namespaces, for
+//! example while migrating to a new name. The first is used when reporting
+//! errors that aren't tied to a specific attribute, and a field marked with
+//! `#[attire(capture = "scope")]` records which one was actually used.
+//! Scopes that are being phased out can be listed with
+//! `#[attire(deprecated_scope = "old")]` instead, which are accepted in the
+//! same way but produce a warning (see [Errors](#errors)). These are only
+//! allowed along with a `scope`.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "new", scope = "old")]
+//! struct Attributes<'a> {
+//!     #[attire(capture = "scope")]
+//!     scope: Option<&'a str>,
+//!     awesome: Option<&'a str>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[old(awesome = \"yes\")]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Attributes {
+//!     scope: Some("old"),
+//!     awesome: Some("yes"),
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
 //! ## Default
 //!
@@ -244,6 +589,7 @@
 //! # impl From<String> for E { fn from(_: String) -> E { E } }
 //! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
 //! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
 //! #[derive(PromAttire, PartialEq, Debug)]
 //! struct Attributes {
 //!     #[attire(default = "yes")]
@@ -265,103 +611,473 @@
 //! # foo().unwrap()
 //! # }
 //! ```
+//!
+//! Alternatively, if there is no sensible default, you can mark a field with
+//! `#[attire(required)]` and `try_from` will return a `Missing` error when
+//! the attribute is not specified.
 
-//! ## More examples **Coming Soon**
+//! ## Duplicates
 //!
-//! For now if you check [the list of examples to
-//! add](https://github.com/Nemo157/prom-attire-rs/issues/5) and [look at the
-//! tests](https://github.com/Nemo157/prom-attire-rs/tree/master/tests) you may
-//! be able to work out how to do what you want.
+//! Specifying the attribute for an `Option` or unwrapped field more than
+//! once returns a `Duplicate` error carrying both items. This can be changed
+//! per field with `#[attire(duplicate = "first")]` or `#[attire(duplicate =
+//! "last")]` to keep the first or last value instead, reporting a warning
+//! for the discarded value. `Vec` and set fields collect every value.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! struct Attributes<'a> {
+//!     strict: Option<&'a str>,
+//!     #[attire(duplicate = "last")]
+//!     lenient: Option<&'a str>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[strict = \"one\"]
+//!     #[strict = \"two\"]
+//!     #[lenient = \"one\"]
+//!     #[lenient = \"two\"]
+//!     struct Foo {}
+//! ")?;
+//! let errs = Attributes::try_from(ast.attrs.as_slice()).unwrap_err();
+//! assert_eq!(errs.len(), 1);
+//! if let AttributesFromAttributesError::Duplicate { attr, .. } = errs[0] {
+//!     assert_eq!(attr, "strict");
+//! }
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
+//! ## Aliases and Deprecation
+//!
+//! When renaming an attribute the old name can be kept working with
+//! `#[attire(alias = "old_name")]`, which may be given multiple times, or
+//! with `#[attire(deprecated_alias = "old_name")]` to also warn that the new
+//! name should be used instead. Whole fields can be marked with
+//! `#[attire(deprecated = "use `other` instead")]`, and scopes with
+//! `#[attire(deprecated_scope = "old")]` on the struct. These are
+//! still parsed as normal, but produce warnings that can be retrieved with
+//! `FromAttributes::try_from_with_warnings` (`try_from` discards them), and
+//! deprecated fields won't be suggested as the replacement for a misspelled
+//! attribute.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "you", deprecated_scope = "thou")]
+//! struct Attributes<'a> {
+//!     #[attire(alias = "awesome", deprecated_alias = "wonderful")]
+//!     amazing: Option<&'a str>,
+//!     #[attire(deprecated = "it is always cool")]
+//!     cool: bool,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[thou(wonderful = \"yes\", cool)]
+//!     struct Foo {}
+//! ")?;
+//! let (attrs, warnings) = Attributes::try_from_with_warnings(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Attributes {
+//!     amazing: Some("yes"),
+//!     cool: true,
+//! });
+//! let warnings = warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+//! assert_eq!(warnings, vec![
+//!     "Attribute scope `thou` is deprecated, use `you` instead",
+//!     "Attribute thou(wonderful) is deprecated, use `amazing` instead",
+//!     "Attribute thou(cool) is deprecated, it is always cool",
+//! ]);
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
-extern crate proc_macro;
-extern crate syn;
-extern crate error_chain;
+//! ## Renaming
+//!
+//! Attribute names are taken from field names, and from variant names in
+//! `snake_case`, unless given with `attribute`. To follow another case
+//! convention without repeating `attribute` on every field, use
+//! `#[attire(rename_all = "...")]` on the struct or enum with one of
+//! `kebab-case`, `camelCase`, `PascalCase` or `SCREAMING_SNAKE_CASE`. This
+//! applies to split parents too, but not to explicit `attribute`s or aliases.
+//! Note that syn currently can't parse attributes containing `-`, so
+//! `kebab-case` keeps the `snake_case` names for now, which are what syn can
+//! parse and what errors will report.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "you", rename_all = "camelCase")]
+//! struct Attributes<'a> {
+//!     first_name: Option<&'a str>,
+//!     #[attire(attribute = "surname")]
+//!     last_name: Option<&'a str>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[you(firstName = \"Ada\", surname = \"Lovelace\")]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Attributes {
+//!     first_name: Some("Ada"),
+//!     last_name: Some("Lovelace"),
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
-#[macro_use]
-extern crate prom_attire_bootstrap;
-extern crate prom_attire_impl;
+//! ## Constraints
+//!
+//! Rules about which attributes can be used together are checked once all
+//! the attributes have been parsed. A field marked with `#[attire(conflicts_with
+//! = "other")]` may not be specified along with the `other` field, and one
+//! marked with `#[attire(requires = "other")]` may only be specified if
+//! `other` is too. For larger groups `#[attire(exclusive = "a, b, c")]` on the
+//! struct allows at most one of the fields to be specified, and
+//! `#[attire(at_least_one = "a, b, c")]` requires at least one of them. A
+//! field counts as specified if its attribute appeared at all, even if the
+//! value is the same as the default.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "you")]
+//! struct Attributes<'a> {
+//!     #[attire(conflicts_with = "rename")]
+//!     skip: bool,
+//!     rename: Option<&'a str>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[you(skip, rename = \"bar\")]
+//!     struct Foo {}
+//! ")?;
+//! let errors = Attributes::try_from(ast.attrs.as_slice()).unwrap_err();
+//! assert_eq!(
+//!     errors[0].to_string(),
+//!     "Conflicting attributes you(skip) and you(rename), only one may be specified");
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
-use error_chain::ChainedError;
+//! ## Bounds
+//!
+//! Integer and float fields can be given a `min` and/or `max`, each value
+//! parsed for the field, including every element of a collection or value of
+//! a map, must be within them. Bounds that aren't valid literals in an
+//! attribute, such as negative numbers, can be given as strings, e.g.
+//! `#[attire(min = "-5")]`.
+//!
+//! String fields can be given a `min_len` and/or `max_len` on the number of
+//! characters in the value. On `Vec`, set and map fields these instead bound
+//! the number of elements, which is checked once all the attributes have been
+//! parsed so applies even if the attribute is not specified at all.
+//! `non_empty` is a shorthand for a `min_len` of 1.
+//!
+//! A value outside its bounds is reported as an `OutOfRange` error.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "you")]
+//! struct Attributes<'a> {
+//!     #[attire(min = 0, max = 10)]
+//!     priority: Option<u8>,
+//!     #[attire(non_empty)]
+//!     derives: Vec<&'a str>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[you(priority = 12)]
+//!     struct Foo {}
+//! ")?;
+//! let errors = Attributes::try_from(ast.attrs.as_slice()).unwrap_err();
+//! assert_eq!(
+//!     errors[0].to_string(),
+//!     "Value 12 for you(priority) is out of range, expected between 0 and 10");
+//! assert_eq!(
+//!     errors[1].to_string(),
+//!     "Length 0 of you(derives) is out of range, expected at least 1");
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
-#[derive(PromAttireBootstrap)]
-struct Attributes<'a> {
-    #[attire_bootstrap(scope)]
-    scope: Option<&'a str>,
-    #[attire_bootstrap(docs)]
-    docs: Option<&'a str>,
-}
+//! ## Validation
+//!
+//! Checks that go beyond parsing can be attached with `#[attire(validate =
+//! "path::to::function")]`. On a field the function is called with a reference
+//! to each value parsed for it, for a map each entry's value, before it is
+//! stored. On the struct or enum itself the function is called with the
+//! finished value, but only if there were no other errors. The function
+//! returns a `Result<(), E>` where the `E` can be converted into a
+//! `Box<Error>`, such as a `String` or `&str`, and is returned as a
+//! `Validation` error along with the attribute it was for.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! fn not_empty(value: &str) -> Result<(), &'static str> {
+//!     if value.is_empty() { Err("must not be empty") } else { Ok(()) }
+//! }
+//!
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "you")]
+//! struct Attributes<'a> {
+//!     #[attire(validate = "not_empty")]
+//!     rename: Option<&'a str>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[you(rename = \"\")]
+//!     struct Foo {}
+//! ")?;
+//! let errors = Attributes::try_from(ast.attrs.as_slice()).unwrap_err();
+//! assert_eq!(
+//!     errors[0].to_string(),
+//!     "Invalid attribute you(rename): must not be empty");
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
+
+//! ## Enums
+//!
+//! When a set of attributes are mutually exclusive you can derive on an enum
+//! instead. Unit variants match a bare word, variants with a single unnamed
+//! field match a name-value attribute and struct variants match a list
+//! containing their fields. Variant names are converted to `snake_case`
+//! unless an `#[attire(attribute = "foo")]` is given, which is the only
+//! option variants accept, and exactly one of the variants must be present.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "mode")]
+//! enum Mode {
+//!     Fast,
+//!     Safe { level: Option<u8> },
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[mode(safe(level = 3))]
+//!     struct Foo {}
+//! ")?;
+//! let mode = Mode::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(mode, Mode::Safe { level: Some(3) });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
+
+//! ## Value Enums
+//!
+//! When a single attribute takes one of a fixed set of values,
+//! `#[derive(AttireValue)]` on a fieldless enum implements `FromStr` for it.
+//! Variant names are converted to `kebab-case`, or the `snake_case` form is
+//! also accepted so that the value can be given as a bare word. A variant can
+//! be renamed with `#[attire(attribute = "foo")]` and given extra names with
+//! `#[attire(alias = "bar")]`, these are the only options a variant accepts
+//! and the enum itself takes none. An unknown value gives an `InvalidChoice`
+//! error listing the valid names.
+//!
+//! Fields of custom types, such as these enums, can be specified with a
+//! string or with the value as a word in a list, e.g. `#[you(mode(fast))]`,
+//! the list must hold a single value unless the field is a collection.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(AttireValue, PartialEq, Debug)]
+//! enum Mode {
+//!     Fast,
+//!     #[attire(alias = "careful")]
+//!     SafeAndSound,
+//! }
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "you")]
+//! struct Attributes {
+//!     mode: Option<Mode>,
+//!     fallback: Option<Mode>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[you(mode(fast), fallback = \"safe-and-sound\")]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Attributes {
+//!     mode: Some(Mode::Fast),
+//!     fallback: Some(Mode::SafeAndSound),
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
-#[derive(PromAttireBootstrap)]
-struct FieldAttributes<'a> {
-    #[attire_bootstrap(field_attribute)]
-    attribute: Option<&'a str>,
-    #[attire_bootstrap(field_split_attribute_of)]
-    split_attribute_of: Option<&'a str>,
-    #[attire_bootstrap(field_default)]
-    default: prom_attire_impl::Defaulted,
-    #[attire_bootstrap(field_flag_value)]
-    flag_value: Option<&'a str>,
-}
+//! ## Nested
+//!
+//! A field can itself be another type deriving `PromAttire` by marking it
+//! with `#[attire(nested)]`, it will then be parsed from the contents of a
+//! list attribute, to whatever depth you need. Errors from within the nested
+//! type will report the full path to the attribute, e.g.
+//! `outer(serde(rename))`.
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! struct Serde<'a> {
+//!     rename: Option<&'a str>,
+//! }
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! #[attire(scope = "outer")]
+//! struct Attributes<'a> {
+//!     #[attire(nested)]
+//!     serde: Option<Serde<'a>>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[outer(serde(rename = \"yes\"))]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::try_from(ast.attrs.as_slice())?;
+//! assert_eq!(attrs, Attributes {
+//!     serde: Some(Serde { rename: Some("yes") }),
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
-/// The procedural macro implementing `#[derive(PromAttire)]`
-#[proc_macro_derive(PromAttire, attributes(attire))]
-pub fn app(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input = input.to_string();
+//! ## Capturing
+//!
+//! As well as the attributes you may want details of the item they are
+//! attached to. Marking a field with `#[attire(capture = "...")]` stores one
+//! of `ident`, `vis`, `ty` or `generics` in it when parsing via the
+//! `from_derive_input`, `from_field` or `from_variant` methods of
+//! `FromAttributes`. Captured fields must be an `Option` as not every item
+//! has every detail available, and `try_from` will always leave them as
+//! `None` (except for `scope`, see [Scoping](#scoping)).
+//!
+//! ```rust
+//! # #[macro_use] extern crate prom_attire;
+//! # extern crate syn;
+//! # fn main() {
+//! # struct E;
+//! # impl ::std::fmt::Debug for E { fn fmt(&self, _: &mut ::std::fmt::Formatter) -> ::std::fmt::Result { Ok(()) } }
+//! # impl From<String> for E { fn from(_: String) -> E { E } }
+//! # impl<T> From<Vec<T>> for E { fn from(_: Vec<T>) -> E { E } }
+//! # fn foo() -> Result<(), E> {
+//! # use prom_attire::FromAttributes;
+//! #[derive(PromAttire, PartialEq, Debug)]
+//! struct Attributes<'a> {
+//!     #[attire(capture = "ident")]
+//!     ident: Option<&'a syn::Ident>,
+//!     awesome: Option<&'a str>,
+//! }
+//! let ast = syn::parse_derive_input("
+//!     #[awesome = \"yes\"]
+//!     struct Foo {}
+//! ")?;
+//! let attrs = Attributes::from_derive_input(&ast)?;
+//! assert_eq!(attrs, Attributes {
+//!     ident: Some(&syn::Ident::new("Foo")),
+//!     awesome: Some("yes"),
+//! });
+//! # Ok(())
+//! # }
+//! # foo().unwrap()
+//! # }
+//! ```
 
-    let ast = match syn::parse_derive_input(&input) {
-        Ok(ast) => ast,
-        Err(err) => {
-            println!("{}", err);
-            panic!("Internal error in prom-attire (probably)");
-        }
-    };
+//! ## More examples **Coming Soon**
+//!
+//! For now if you check [the list of examples to
+//! add](https://github.com/Nemo157/prom-attire-rs/issues/5) and [look at the
+//! tests](https://github.com/Nemo157/prom-attire-rs/tree/master/tests) you may
+//! be able to work out how to do what you want.
 
-    let attrs = match Attributes::try_from(ast.attrs.as_slice()) {
-        Ok(attrs) => attrs,
-        Err(errs) => {
-            for err in errs {
-                println!("{}", err);
-            }
-            panic!("Invalid attributes specified for prom-attire macro");
-        }
-    };
+extern crate prom_attire_derive;
+extern crate prom_attire_impl;
 
-    let config = prom_attire_impl::Config {
-        scope: attrs.scope,
-        docs: attrs.docs,
-        parse_field_config: &|attrs| {
-            let attrs = match FieldAttributes::try_from(attrs) {
-                Ok(attrs) => attrs,
-                Err(errs) => {
-                    for err in errs {
-                        println!("{}", err);
-                    }
-                    panic!("Invalid attributes specified for prom-attire macro");
-                }
-            };
-            prom_attire_impl::FieldConfig {
-                attribute: attrs.attribute,
-                split_attribute_of: attrs.split_attribute_of,
-                default: attrs.default,
-                flag_value: attrs.flag_value,
-            }
-        }
-    };
+pub use prom_attire_derive::{PromAttire, AttireValue};
 
-    let expanded = match prom_attire_impl::derive(&input, &config) {
-        Ok(expanded) => expanded,
-        Err(err) => {
-            println!("{}", err.display());
-            panic!("Expanding prom-attire failed");
-        }
-    };
+pub use prom_attire_impl::{FromAttributes, Warning, InvalidChoice};
 
-    match expanded.parse() {
-        Ok(parsed) => parsed,
-        Err(err) => {
-            println!("{:?}", err);
-            panic!("Internal error in prom-attire");
-        }
-    }
-}
+#[doc(hidden)]
+pub use prom_attire_impl::runtime;
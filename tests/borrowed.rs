@@ -0,0 +1,101 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use std::borrow::Cow;
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn lit() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A<'a> {
+        default: Option<&'a syn::Lit>,
+        choices: Vec<&'a syn::Lit>,
+    }
+    let input = quote! {
+        #[default = 5]
+        #[choices("a", 'b')]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        default: Some(&syn::Lit::Int(5, syn::IntTy::Unsuffixed)),
+        choices: vec![&syn::Lit::from("a"), &syn::Lit::from('b')],
+    });
+}
+
+#[test]
+fn meta_item() {
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A<'a> {
+        derive: Option<&'a syn::MetaItem>,
+        serde: Vec<&'a [syn::NestedMetaItem]>,
+    }
+    let input = quote! {
+        #[carrot(derive(Debug, Clone))]
+        #[carrot(serde(rename = "b"), serde(skip))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    let derive = attrs.derive.unwrap();
+    assert_eq!(derive.name(), "derive");
+    match *derive {
+        syn::MetaItem::List(_, ref items) => assert_eq!(items.len(), 2),
+        ref item => panic!("unexpected item {:?}", item),
+    }
+    assert_eq!(attrs.serde.len(), 2);
+    assert_eq!(attrs.serde[0].len(), 1);
+    assert_eq!(attrs.serde[1], &[
+        syn::NestedMetaItem::MetaItem(syn::MetaItem::Word("skip".into())),
+    ][..]);
+}
+
+#[test]
+fn cow_str() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A<'a> {
+        name: Option<Cow<'a, str>>,
+        size: Option<Cow<'a, str>>,
+    }
+    let input = quote! {
+        #[name = "carrot"]
+        #[size = 5]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    match attrs.name {
+        Some(Cow::Borrowed("carrot")) => (),
+        ref name => panic!("unexpected name {:?}", name),
+    }
+    match attrs.size {
+        Some(Cow::Owned(ref size)) if size == "5" => (),
+        ref size => panic!("unexpected size {:?}", size),
+    }
+}
+
+#[test]
+fn extra_lifetimes() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A<'a, 'b> {
+        name: Option<&'a str>,
+        alias: Option<&'b str>,
+    }
+    let input = quote! {
+        #[name = "carrot"]
+        #[alias = "root"]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        name: Some("carrot"),
+        alias: Some("root"),
+    });
+}
@@ -0,0 +1,157 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use std::collections::HashMap;
+
+use prom_attire::FromAttributes;
+
+#[derive(PromAttire, Debug, PartialEq)]
+#[attire(scope = "carrot")]
+struct A<'a> {
+    #[attire(min = 0, max = 10)]
+    priority: Option<u8>,
+    #[attire(min = "-5")]
+    offset: Option<i32>,
+    #[attire(max = 2.5)]
+    ratio: Option<f64>,
+    #[attire(non_empty, max_len = 6)]
+    name: Option<&'a str>,
+    #[attire(min_len = 1, max_len = 2)]
+    colours: Vec<&'a str>,
+    #[attire(min = 1)]
+    sizes: Vec<u8>,
+    #[attire(max = 100)]
+    weights: HashMap<&'a str, u32>,
+}
+
+fn errors(input: quote::Tokens) -> Vec<String> {
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let result = A::try_from(ast.attrs.as_slice());
+    match result {
+        Ok(attrs) => panic!("unexpected success {:?}", attrs),
+        Err(errs) => errs.iter().map(|err| err.to_string()).collect(),
+    }
+}
+
+#[test]
+fn within() {
+    let input = quote! {
+        #[carrot(priority = 10, offset = "-5", ratio = 2.5, name = "nantes")]
+        #[carrot(colours("orange", "purple"), sizes(1, 2), weights(small = 100))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        priority: Some(10),
+        offset: Some(-5),
+        ratio: Some(2.5),
+        name: Some("nantes"),
+        colours: vec!["orange", "purple"],
+        sizes: vec![1, 2],
+        weights: vec![("small", 100)].into_iter().collect(),
+    });
+}
+
+#[test]
+fn values() {
+    let input = quote! {
+        #[carrot(priority = 11, offset = "-6", ratio = 3.0, colours = "orange")]
+        #[carrot(sizes(0, 1), weights(small = 101))]
+        struct C {}
+    };
+    assert_eq!(errors(input), vec![
+        "Value 11 for carrot(priority) is out of range, expected between 0 and 10",
+        "Value -6 for carrot(offset) is out of range, expected at least -5",
+        "Value 3 for carrot(ratio) is out of range, expected at most 2.5",
+        "Value 0 for carrot(sizes) is out of range, expected at least 1",
+        "Value 101 for carrot(weights(small)) is out of range, expected at most 100",
+    ]);
+}
+
+#[test]
+fn string_length() {
+    let input = quote! {
+        #[carrot(name = "", colours = "orange")]
+        struct C {}
+    };
+    assert_eq!(errors(input), vec![
+        "Length 0 of carrot(name) is out of range, expected between 1 and 6",
+    ]);
+
+    let input = quote! {
+        #[carrot(name = "chantenay", colours = "orange")]
+        struct C {}
+    };
+    assert_eq!(errors(input), vec![
+        "Length 9 of carrot(name) is out of range, expected between 1 and 6",
+    ]);
+}
+
+#[test]
+fn collection_length() {
+    let input = quote! {
+        #[carrot(priority = 1)]
+        struct C {}
+    };
+    assert_eq!(errors(input), vec![
+        "Length 0 of carrot(colours) is out of range, expected between 1 and 2",
+    ]);
+
+    let input = quote! {
+        #[carrot(colours("orange", "purple", "white"))]
+        struct C {}
+    };
+    assert_eq!(errors(input), vec![
+        "Length 3 of carrot(colours) is out of range, expected between 1 and 2",
+    ]);
+}
+
+#[test]
+fn variant() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    enum Mode {
+        Slow {
+            #[attire(min = 1, max = 5)]
+            speed: Option<u8>,
+        },
+    }
+    let input = quote! {
+        #[slow(speed = 9)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = Mode::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(
+        errs[0].to_string(),
+        "Value 9 for slow(speed) is out of range, expected between 1 and 5");
+}
+
+#[test]
+fn owned_string() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct B {
+        #[attire(max_len = 3)]
+        label: Option<String>,
+    }
+    let input = quote! {
+        #[label = "ÿÿÿ"]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = B::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, B { label: Some("ÿÿÿ".to_owned()) });
+
+    let input = quote! {
+        #[label = "long"]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = B::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(
+        errs[0].to_string(),
+        "Length 4 of label is out of range, expected at most 3");
+}
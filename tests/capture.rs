@@ -0,0 +1,149 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn derive_input() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A<'a> {
+        #[attire(capture = "ident")]
+        ident: Option<&'a syn::Ident>,
+        #[attire(capture = "vis")]
+        vis: Option<&'a syn::Visibility>,
+        #[attire(capture = "generics")]
+        generics: Option<&'a syn::Generics>,
+        #[attire(capture = "ty")]
+        ty: Option<&'a syn::Ty>,
+        colour: Option<&'a str>,
+    }
+    let input = quote! {
+        #[carrot(colour = "orange")]
+        pub struct C<T> { t: T }
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::from_derive_input(&ast).unwrap();
+    assert_eq!(attrs, A {
+        ident: Some(&ast.ident),
+        vis: Some(&syn::Visibility::Public),
+        generics: Some(&ast.generics),
+        ty: None,
+        colour: Some("orange"),
+    });
+}
+
+#[test]
+fn field() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A<'a> {
+        #[attire(capture = "ident")]
+        ident: Option<&'a syn::Ident>,
+        #[attire(capture = "vis")]
+        vis: Option<&'a syn::Visibility>,
+        #[attire(capture = "ty")]
+        ty: Option<&'a syn::Ty>,
+        #[attire(capture = "generics")]
+        generics: Option<&'a syn::Generics>,
+        colour: Option<&'a str>,
+    }
+    let input = quote! {
+        struct C {
+            #[carrot(colour = "purple")]
+            d: u8,
+            e: u16,
+        }
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let fields = match ast.body {
+        syn::Body::Struct(syn::VariantData::Struct(ref fields)) => fields,
+        _ => unreachable!(),
+    };
+    let attrs = fields.iter()
+        .map(|field| A::from_field(field).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(attrs, vec![
+        A {
+            ident: fields[0].ident.as_ref(),
+            vis: Some(&syn::Visibility::Inherited),
+            ty: Some(&fields[0].ty),
+            generics: None,
+            colour: Some("purple"),
+        },
+        A {
+            ident: fields[1].ident.as_ref(),
+            vis: Some(&syn::Visibility::Inherited),
+            ty: Some(&fields[1].ty),
+            generics: None,
+            colour: None,
+        },
+    ]);
+}
+
+#[test]
+fn variant() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A<'a> {
+        #[attire(capture = "ident")]
+        ident: Option<&'a syn::Ident>,
+        #[attire(capture = "vis")]
+        vis: Option<&'a syn::Visibility>,
+        colour: Option<&'a str>,
+    }
+    let input = quote! {
+        enum C {
+            #[carrot(colour = "white")]
+            D,
+        }
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let variants = match ast.body {
+        syn::Body::Enum(ref variants) => variants,
+        _ => unreachable!(),
+    };
+    let attrs = A::from_variant(&variants[0]).unwrap();
+    assert_eq!(attrs, A {
+        ident: Some(&variants[0].ident),
+        vis: None,
+        colour: Some("white"),
+    });
+}
+
+#[test]
+fn not_captured_by_try_from() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A<'a> {
+        #[attire(capture = "ident")]
+        ident: Option<&'a syn::Ident>,
+        colour: Option<&'a str>,
+    }
+    let input = quote! {
+        #[carrot(colour = "orange")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { ident: None, colour: Some("orange") });
+}
+
+#[test]
+fn without_captures() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode")]
+    enum Mode {
+        Fast,
+        Safe,
+    }
+    let input = quote! {
+        #[mode(fast)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    assert_eq!(Mode::from_derive_input(&ast).unwrap(), Mode::Fast);
+}
@@ -0,0 +1,132 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+#[derive(PromAttire, Debug, PartialEq)]
+#[attire(scope = "carrot")]
+#[attire(exclusive = "raw, cooked, roasted", at_least_one = "colour, size")]
+struct A<'a> {
+    #[attire(conflicts_with = "rename")]
+    skip: bool,
+    #[attire(conflicts_with = "skip")]
+    rename: Option<&'a str>,
+    #[attire(requires = "bound")]
+    with: Option<&'a str>,
+    bound: Vec<&'a str>,
+    raw: bool,
+    cooked: bool,
+    roasted: bool,
+    colour: Option<&'a str>,
+    size: Option<u8>,
+}
+
+fn errors(input: quote::Tokens) -> Vec<String> {
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let result = A::try_from(ast.attrs.as_slice());
+    match result {
+        Ok(attrs) => panic!("unexpected success {:?}", attrs),
+        Err(errs) => errs.iter().map(|err| err.to_string()).collect(),
+    }
+}
+
+#[test]
+fn satisfied() {
+    let input = quote! {
+        #[carrot(rename = "turnip", with = "soil", bound = "deep", raw)]
+        #[carrot(size = 3)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        skip: false,
+        rename: Some("turnip"),
+        with: Some("soil"),
+        bound: vec!["deep"],
+        raw: true,
+        cooked: false,
+        roasted: false,
+        colour: None,
+        size: Some(3),
+    });
+}
+
+#[test]
+fn conflicts_with() {
+    let input = quote! {
+        #[carrot(skip, rename = "turnip", colour = "orange")]
+        struct C {}
+    };
+    assert_eq!(errors(input), vec![
+        "Conflicting attributes carrot(skip) and carrot(rename), only one may be specified",
+    ]);
+}
+
+#[test]
+fn conflicts_even_if_false() {
+    let input = quote! {
+        #[carrot(skip = false, rename = "turnip", colour = "orange")]
+        struct C {}
+    };
+    assert_eq!(errors(input).len(), 1);
+}
+
+#[test]
+fn requires() {
+    let input = quote! {
+        #[carrot(with = "soil", colour = "orange")]
+        struct C {}
+    };
+    assert_eq!(errors(input), vec![
+        "Attribute carrot(with) requires carrot(bound) to also be specified",
+    ]);
+}
+
+#[test]
+fn exclusive() {
+    let input = quote! {
+        #[carrot(raw, roasted, cooked, colour = "orange")]
+        struct C {}
+    };
+    assert_eq!(errors(input), vec![
+        "Conflicting attributes carrot(raw) and carrot(cooked), only one may be specified",
+    ]);
+}
+
+#[test]
+fn at_least_one() {
+    let input = quote! {
+        #[carrot(raw)]
+        struct C {}
+    };
+    assert_eq!(errors(input), vec![
+        "Missing attribute under carrot, expected at least one of `colour`, `size`",
+    ]);
+}
+
+#[test]
+fn variant() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    enum Mode {
+        Fast {
+            #[attire(conflicts_with = "safe")]
+            reckless: bool,
+            safe: bool,
+        },
+    }
+    let input = quote! {
+        #[fast(reckless, safe)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = Mode::try_from(ast.attrs.as_slice()).unwrap_err();
+    let errs = errs.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(errs, vec![
+        "Conflicting attributes fast(reckless) and fast(safe), only one may be specified",
+        "Missing attribute, expected one of `fast`",
+    ]);
+}
@@ -0,0 +1,174 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+#[derive(PromAttire, Debug, PartialEq)]
+#[attire(scope = "carrot", deprecated_scope = "parsnip")]
+struct A<'a> {
+    #[attire(alias = "color", alias = "hue")]
+    colour: Option<&'a str>,
+    #[attire(deprecated = "use `size` instead")]
+    length: Option<u8>,
+    size: Option<u8>,
+    #[attire(nested)]
+    leaf: Option<B>,
+}
+
+#[derive(PromAttire, Debug, PartialEq)]
+struct B {
+    #[attire(alias = "colour", deprecated = "leaves are always green")]
+    green: bool,
+}
+
+#[test]
+fn aliases() {
+    let input = quote! {
+        #[carrot(color = "orange", size = 3)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let (attrs, warnings) = A::try_from_with_warnings(ast.attrs.as_slice())
+        .unwrap();
+    assert_eq!(attrs, A {
+        colour: Some("orange"),
+        length: None,
+        size: Some(3),
+        leaf: None,
+    });
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+#[test]
+fn alias_duplicate() {
+    let input = quote! {
+        #[carrot(colour = "orange", hue = "red")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errors = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Duplicate attribute carrot(hue), it may only be specified once");
+}
+
+#[test]
+fn deprecated_field() {
+    let input = quote! {
+        #[carrot(length = 4)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let (attrs, warnings) = A::try_from_with_warnings(ast.attrs.as_slice())
+        .unwrap();
+    assert_eq!(attrs, A {
+        colour: None,
+        length: Some(4),
+        size: None,
+        leaf: None,
+    });
+    let warnings = warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+    assert_eq!(warnings, vec![
+        "Attribute carrot(length) is deprecated, use `size` instead",
+    ]);
+}
+
+#[test]
+fn deprecated_not_suggested() {
+    let input = quote! {
+        #[carrot(lenght = 4)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errors = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "Unknown attribute `carrot(lenght)`");
+}
+
+#[test]
+fn deprecated_scope() {
+    let input = quote! {
+        #[parsnip(size = 4)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let (attrs, warnings) = A::try_from_with_warnings(ast.attrs.as_slice())
+        .unwrap();
+    assert_eq!(attrs, A {
+        colour: None,
+        length: None,
+        size: Some(4),
+        leaf: None,
+    });
+    let warnings = warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+    assert_eq!(warnings, vec![
+        "Attribute scope `parsnip` is deprecated, use `carrot` instead",
+    ]);
+}
+
+#[test]
+fn nested() {
+    let input = quote! {
+        #[carrot(leaf(colour))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let (attrs, warnings) = A::try_from_with_warnings(ast.attrs.as_slice())
+        .unwrap();
+    assert_eq!(attrs, A {
+        colour: None,
+        length: None,
+        size: None,
+        leaf: Some(B { green: true }),
+    });
+    let warnings = warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+    assert_eq!(warnings, vec![
+        "Attribute carrot(leaf(colour)) is deprecated, leaves are always green",
+    ]);
+}
+
+#[test]
+fn ignored_by_try_from() {
+    let input = quote! {
+        #[parsnip(length = 4)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs.length, Some(4));
+}
+
+#[test]
+fn deprecated_alias() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct D {
+        #[attire(alias = "girth", deprecated_alias = "thickness")]
+        width: Option<u8>,
+    }
+
+    for name in &["width", "girth"] {
+        let ast = syn::parse_derive_input(&format!("#[carrot({} = 2)] struct C {{}}", name)).unwrap();
+        let (attrs, warnings) = D::try_from_with_warnings(ast.attrs.as_slice())
+            .unwrap();
+        assert_eq!(attrs, D { width: Some(2) });
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    let input = quote! {
+        #[carrot(thickness = 2)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let (attrs, warnings) = D::try_from_with_warnings(ast.attrs.as_slice())
+        .unwrap();
+    assert_eq!(attrs, D { width: Some(2) });
+    let warnings = warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+    assert_eq!(warnings, vec![
+        "Attribute carrot(thickness) is deprecated, use `width` instead",
+    ]);
+}
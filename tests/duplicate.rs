@@ -0,0 +1,153 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn option_duplicate_errors() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    struct A<'a> {
+        a: Option<&'a str>,
+    }
+    let input = quote! {
+        #[a = "1"]
+        #[a = "2"]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    match errs[0] {
+        AFromAttributesError::Duplicate { ref scope, attr, first, second } => {
+            assert!(scope.is_empty());
+            assert_eq!(attr, "a");
+            assert_eq!(first, &ast.attrs[0].value);
+            assert_eq!(second, &ast.attrs[1].value);
+        }
+        ref err => panic!("unexpected error {:?}", err),
+    }
+    assert_eq!(
+        errs[0].to_string(),
+        "Duplicate attribute a, it may only be specified once");
+}
+
+#[test]
+fn unwrapped_duplicate_errors() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A {
+        #[attire(default)]
+        a: u8,
+        b: bool,
+    }
+    let input = quote! {
+        #[carrot(a = 1, b, a = 2)]
+        #[carrot(b = false)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    let errs = errs.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(errs, [
+        "Duplicate attribute carrot(a), it may only be specified once",
+        "Duplicate attribute carrot(b), it may only be specified once",
+    ]);
+}
+
+#[test]
+fn duplicate_first() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A<'a> {
+        #[attire(duplicate = "first")]
+        a: Option<&'a str>,
+        #[attire(default, duplicate = "first")]
+        b: u8,
+    }
+    let input = quote! {
+        #[a = "1"]
+        #[b = 1]
+        #[a = "2"]
+        #[b = 2]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let (attrs, warnings) = A::try_from_with_warnings(ast.attrs.as_slice())
+        .unwrap();
+    assert_eq!(attrs, A { a: Some("1"), b: 1 });
+    let warnings = warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+    assert_eq!(warnings, [
+        "Duplicate attribute a, only the first value is used",
+        "Duplicate attribute b, only the first value is used",
+    ]);
+}
+
+#[test]
+fn duplicate_last() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A<'a> {
+        #[attire(duplicate = "last")]
+        a: Option<&'a str>,
+        #[attire(default, duplicate = "last")]
+        b: u8,
+    }
+    let input = quote! {
+        #[a = "1"]
+        #[b = 1]
+        #[a = "2"]
+        #[b = 2]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let (attrs, warnings) = A::try_from_with_warnings(ast.attrs.as_slice())
+        .unwrap();
+    assert_eq!(attrs, A { a: Some("2"), b: 2 });
+    let warnings = warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+    assert_eq!(warnings, [
+        "Duplicate attribute a, the last value is used",
+        "Duplicate attribute b, the last value is used",
+    ]);
+}
+
+#[test]
+fn vec_collects_duplicates() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A<'a> {
+        a: Vec<&'a str>,
+    }
+    let input = quote! {
+        #[a = "1"]
+        #[a = "2"]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { a: vec!["1", "2"] });
+}
+
+#[test]
+fn split_duplicate_errors() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A {
+        #[attire(split_attribute_of = "a")]
+        b: Option<bool>,
+        #[attire(split_attribute_of = "a")]
+        c: Option<bool>,
+    }
+    let input = quote! {
+        #[carrot(a = false, a(b = true))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(
+        errs[0].to_string(),
+        "Duplicate attribute carrot(a(b)), it may only be specified once");
+}
@@ -0,0 +1,138 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn unit_variant() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode")]
+    enum Mode {
+        Fast,
+        Safe,
+    }
+    let input = quote! {
+        #[mode(fast)]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = Mode::from(ast.attrs.as_slice());
+    assert_eq!(attrs, Mode::Fast);
+}
+
+#[test]
+fn struct_variant() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode")]
+    enum Mode<'a> {
+        Fast,
+        Safe {
+            level: Option<u8>,
+            name: Option<&'a str>,
+        },
+    }
+    let input = quote! {
+        #[mode(safe(level = 3))]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = Mode::from(ast.attrs.as_slice());
+    assert_eq!(attrs,
+               Mode::Safe {
+                   level: Some(3),
+                   name: None,
+               });
+}
+
+#[test]
+fn name_value_variant() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    enum Mode {
+        Fast,
+        Level(u8),
+    }
+    let input = quote! {
+        #[level = "4"]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = Mode::from(ast.attrs.as_slice());
+    assert_eq!(attrs, Mode::Level(4));
+}
+
+#[test]
+fn renamed_variant() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode")]
+    enum Mode {
+        #[attire(attribute = "quick")]
+        Fast,
+        ReallySafe,
+    }
+    let ast = syn::parse_derive_input("#[mode(quick)] struct S {}").unwrap();
+    assert_eq!(Mode::from(ast.attrs.as_slice()), Mode::Fast);
+    let ast = syn::parse_derive_input("#[mode(really_safe)] struct S {}")
+        .unwrap();
+    assert_eq!(Mode::from(ast.attrs.as_slice()), Mode::ReallySafe);
+}
+
+#[test]
+fn missing_variant() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode")]
+    enum Mode {
+        Fast,
+        Safe,
+    }
+    let input = quote! {
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = Mode::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].to_string(),
+               "Missing attribute under mode, expected one of `fast`, `safe`");
+}
+
+#[test]
+fn conflicting_variants() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode")]
+    enum Mode {
+        Fast,
+        Safe,
+    }
+    let input = quote! {
+        #[mode(fast, safe)]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = Mode::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].to_string(),
+               "Conflicting attributes mode(fast) and mode(safe), only one \
+                may be specified");
+}
+
+#[test]
+fn acronym_variants() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode")]
+    enum Mode {
+        IOError,
+        HTTPServer,
+        UseHTTP,
+    }
+    let names = [
+        ("io_error", Mode::IOError),
+        ("http_server", Mode::HTTPServer),
+        ("use_http", Mode::UseHTTP),
+    ];
+    for &(name, ref mode) in &names {
+        let ast = syn::parse_derive_input(&format!("#[mode({})] struct S {{}}", name)).unwrap();
+        assert_eq!(&Mode::from(ast.attrs.as_slice()), mode);
+    }
+}
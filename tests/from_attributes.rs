@@ -0,0 +1,63 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+fn parse_all<'a, T: FromAttributes<'a>>(ast: &'a syn::DeriveInput) -> Result<T, Vec<T::Error>> {
+    T::try_from(ast.attrs.as_slice())
+}
+
+#[test]
+fn generic_struct() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A<'a> {
+        a: Option<&'a str>,
+        b: Option<bool>,
+    }
+    let input = quote! {
+        #[a = "foo"]
+        #[b = false]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs: A = parse_all(&ast).unwrap();
+    assert_eq!(attrs, A { a: Some("foo"), b: Some(false) });
+}
+
+#[test]
+fn generic_enum() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode")]
+    enum Mode {
+        Fast,
+        Safe,
+    }
+    let input = quote! {
+        #[mode(safe)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let mode: Mode = parse_all(&ast).unwrap();
+    assert_eq!(mode, Mode::Safe);
+}
+
+#[test]
+fn generic_error() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A {
+        a: Option<i32>,
+    }
+    let input = quote! {
+        #[a = "foo"]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = parse_all::<A>(&ast).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(
+        errs[0].to_string(),
+        "Parsing attribute value \"foo\" into a i32 for a failed: invalid digit found in string");
+}
@@ -0,0 +1,127 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn type_parameter() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct Rename<T> {
+        rename: Option<T>,
+    }
+    let input = quote! {
+        #[rename = "5"]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = Rename::<u32>::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, Rename { rename: Some(5) });
+    let attrs = Rename::<String>::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, Rename { rename: Some("5".to_owned()) });
+}
+
+#[test]
+fn declared_bounds_and_lifetime() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A<'a, T: FromStr + Debug, U> where U: Ord {
+        name: Option<&'a str>,
+        #[attire(default)]
+        level: T,
+        sizes: BTreeSet<U>,
+    }
+    let input = quote! {
+        #[carrot(name = "n", level = 3, sizes(2, 1))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::<u8, i64>::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        name: Some("n"),
+        level: 3,
+        sizes: [1, 2].iter().cloned().collect(),
+    });
+}
+
+#[test]
+fn nested_type_parameter() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct Inner<'a> {
+        a: Option<&'a str>,
+    }
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct Outer<T> {
+        #[attire(nested)]
+        inner: Option<T>,
+    }
+    let input = quote! {
+        #[inner(a = "b")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = Outer::<Inner>::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, Outer { inner: Some(Inner { a: Some("b") }) });
+}
+
+#[test]
+fn enum_type_parameter() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode")]
+    enum Mode<T> {
+        Fast,
+        Limit(T),
+    }
+    let input = quote! {
+        #[mode(limit = 10)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let mode = Mode::<u16>::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(mode, Mode::Limit(10));
+}
+
+#[derive(Debug, PartialEq)]
+struct Wrapped<T>(T);
+
+trait Parse: Sized {
+    fn parse(s: &str) -> Option<Self>;
+}
+
+impl Parse for u8 {
+    fn parse(s: &str) -> Option<u8> {
+        s.parse().ok()
+    }
+}
+
+impl<T: Parse> FromStr for Wrapped<T> {
+    type Err = std::num::ParseIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match T::parse(s) {
+            Some(value) => Ok(Wrapped(value)),
+            None => "x".parse::<u8>().map(|_| unreachable!()),
+        }
+    }
+}
+
+#[test]
+fn bound_override() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(bound = "T: Parse")]
+    struct A<T> {
+        a: Option<Wrapped<T>>,
+    }
+    let input = quote! {
+        #[a = 7]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::<u8>::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { a: Some(Wrapped(7)) });
+}
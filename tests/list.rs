@@ -0,0 +1,107 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use std::collections::{BTreeSet, HashSet};
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn vec_of_words() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A<'a> {
+        derives: Vec<&'a str>,
+    }
+    let input = quote! {
+        #[derives(Debug, Clone)]
+        #[derives = "Copy"]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { derives: vec!["Debug", "Clone", "Copy"] });
+}
+
+#[test]
+fn vec_of_literals() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A {
+        sizes: Vec<u8>,
+        names: Vec<String>,
+    }
+    let input = quote! {
+        #[carrot(sizes(1, 2, 3), names("a", b))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        sizes: vec![1, 2, 3],
+        names: vec!["a".to_owned(), "b".to_owned()],
+    });
+}
+
+#[test]
+fn vec_of_syn_paths() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A {
+        derives: Vec<syn::Path>,
+    }
+    let input = quote! {
+        #[derives(Debug, "std::hash::Hash")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        derives: vec![
+            syn::parse_path("Debug").unwrap(),
+            syn::parse_path("std::hash::Hash").unwrap(),
+        ],
+    });
+}
+
+#[test]
+fn sets() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A<'a> {
+        hash: HashSet<&'a str>,
+        btree: BTreeSet<u32>,
+    }
+    let input = quote! {
+        #[carrot(hash(a, b, a), btree(3, 1))]
+        #[carrot(hash = "c", btree = 2)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        hash: ["a", "b", "c"].iter().cloned().collect(),
+        btree: [1, 2, 3].iter().cloned().collect(),
+    });
+}
+
+#[test]
+fn list_item_errors() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A {
+        sizes: Vec<u8>,
+    }
+    let input = quote! {
+        #[carrot(sizes(1, a = 2, "x", 3))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    let errs = errs.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(errs, [
+        "Unexpected item in list attribute carrot(sizes), expected a u8",
+        "Parsing attribute value \"x\" (element 2) into a u8 for carrot(sizes) failed: invalid digit found in string",
+    ]);
+}
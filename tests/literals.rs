@@ -4,6 +4,57 @@ extern crate prom_attire;
 extern crate quote;
 extern crate syn;
 
+use std::fmt;
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+use prom_attire::FromAttributes;
+
+#[derive(Debug, PartialEq)]
+struct Seconds(u64);
+
+impl FromStr for Seconds {
+    type Err = std::num::ParseIntError;
+    fn from_str(s: &str) -> Result<Seconds, Self::Err> {
+        s.parse().map(Seconds)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Ratio(f64);
+
+impl FromStr for Ratio {
+    type Err = std::num::ParseFloatError;
+    fn from_str(s: &str) -> Result<Ratio, Self::Err> {
+        s.parse().map(Ratio)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Toggle(bool);
+
+#[derive(Debug)]
+struct ToggleError;
+
+impl fmt::Display for ToggleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid toggle")
+    }
+}
+
+impl std::error::Error for ToggleError {}
+
+impl FromStr for Toggle {
+    type Err = ToggleError;
+    fn from_str(s: &str) -> Result<Toggle, Self::Err> {
+        match s {
+            "true" | "on" => Ok(Toggle(true)),
+            "false" | "off" => Ok(Toggle(false)),
+            _ => Err(ToggleError),
+        }
+    }
+}
+
 #[test]
 fn literal_string() {
     #[derive(PromAttire)]
@@ -243,3 +294,74 @@ fn literal_bool() {
     let attrs = A::from(ast.attrs.as_slice());
     assert_eq!(attrs.b, Some(true));
 }
+
+#[test]
+fn literal_int_custom() {
+    #[derive(PromAttire)]
+    struct A {
+        b: Option<Seconds>,
+        c: Option<NonZeroU32>,
+    }
+    let input = quote! {
+        #[b = 30]
+        #[c = 5]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::from(ast.attrs.as_slice());
+    assert_eq!(attrs.b, Some(Seconds(30)));
+    assert_eq!(attrs.c, NonZeroU32::new(5));
+}
+
+#[test]
+fn literal_float_custom() {
+    #[derive(PromAttire)]
+    struct A {
+        b: Option<Ratio>,
+    }
+    let input = quote! {
+        #[b = 0.5]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::from(ast.attrs.as_slice());
+    assert_eq!(attrs.b, Some(Ratio(0.5)));
+}
+
+#[test]
+fn literal_bool_custom() {
+    #[derive(PromAttire)]
+    struct A {
+        b: Option<Toggle>,
+    }
+    let input = quote! {
+        #[b = true]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::from(ast.attrs.as_slice());
+    assert_eq!(attrs.b, Some(Toggle(true)));
+}
+
+#[test]
+fn literal_custom_parse_error() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    struct A {
+        b: Option<NonZeroU32>,
+    }
+    let input = quote! {
+        #[b = 0]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    match errs[0] {
+        AFromAttributesError::Parsing { ref value, attr, .. } => {
+            assert_eq!(value, "0");
+            assert_eq!(attr, "b");
+        }
+        ref err => panic!("unexpected error {:?}", err),
+    }
+}
@@ -0,0 +1,123 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use std::collections::{BTreeMap, HashMap};
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn hash_map() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A<'a> {
+        env: HashMap<&'a str, &'a str>,
+    }
+    let input = quote! {
+        #[env(KEY = "value", OTHER = "x")]
+        #[env(LAST = "y")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    let mut env = HashMap::new();
+    env.insert("KEY", "value");
+    env.insert("OTHER", "x");
+    env.insert("LAST", "y");
+    assert_eq!(attrs, A { env: env });
+}
+
+#[test]
+fn btree_map() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A {
+        sizes: BTreeMap<String, u8>,
+    }
+    let input = quote! {
+        #[carrot(sizes(small = 1, large = "3"))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    let mut sizes = BTreeMap::new();
+    sizes.insert("small".to_owned(), 1);
+    sizes.insert("large".to_owned(), 3);
+    assert_eq!(attrs, A { sizes: sizes });
+}
+
+#[test]
+fn duplicate_key_errors() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A {
+        sizes: BTreeMap<String, u8>,
+    }
+    let input = quote! {
+        #[carrot(sizes(small = 1, small = 2))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    match errs[0] {
+        AFromAttributesError::DuplicateKey { ref scope, key } => {
+            assert_eq!(scope, &["carrot", "sizes"]);
+            assert_eq!(key, "small");
+        }
+        ref err => panic!("unexpected error {:?}", err),
+    }
+    assert_eq!(
+        errs[0].to_string(),
+        "Duplicate key carrot(sizes(small)), it may only be specified once");
+}
+
+#[test]
+fn duplicate_key_policies() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A {
+        #[attire(duplicate = "first")]
+        first: BTreeMap<String, u8>,
+        #[attire(duplicate = "last")]
+        last: BTreeMap<String, u8>,
+    }
+    let input = quote! {
+        #[first(a = 1, a = 2)]
+        #[last(a = 1)]
+        #[last(a = 2)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let (attrs, warnings) = A::try_from_with_warnings(ast.attrs.as_slice())
+        .unwrap();
+    assert_eq!(attrs.first["a"], 1);
+    assert_eq!(attrs.last["a"], 2);
+    let warnings = warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+    assert_eq!(warnings, [
+        "Duplicate key first(a), only the first value is used",
+        "Duplicate key last(a), the last value is used",
+    ]);
+}
+
+#[test]
+fn entry_errors() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A {
+        sizes: HashMap<String, u8>,
+    }
+    let input = quote! {
+        #[carrot(sizes(small, large = "x"))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    let errs = errs.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(errs, [
+        "Unexpected item in list attribute carrot(sizes), expected a `key = value` entry",
+        "Parsing attribute value \"x\" into a u8 for carrot(sizes(large)) failed: invalid digit found in string",
+    ]);
+}
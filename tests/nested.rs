@@ -0,0 +1,146 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn nested_struct() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct Inner<'a> {
+        rename: Option<&'a str>,
+        skip: bool,
+    }
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "outer")]
+    struct Outer<'a> {
+        #[attire(nested)]
+        serde: Option<Inner<'a>>,
+        other: Option<&'a str>,
+    }
+    let input = quote! {
+        #[outer(serde(rename = "b", skip), other = "c")]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = Outer::from(ast.attrs.as_slice());
+    assert_eq!(attrs,
+               Outer {
+                   serde: Some(Inner {
+                       rename: Some("b"),
+                       skip: true,
+                   }),
+                   other: Some("c"),
+               });
+}
+
+#[test]
+fn nested_missing() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct Inner {
+        skip: bool,
+    }
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct Outer {
+        #[attire(nested)]
+        serde: Option<Inner>,
+        #[attire(nested, default)]
+        defaulted: Inner,
+    }
+    impl Default for Inner {
+        fn default() -> Inner {
+            Inner { skip: true }
+        }
+    }
+    let input = quote! {
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = Outer::from(ast.attrs.as_slice());
+    assert_eq!(attrs,
+               Outer {
+                   serde: None,
+                   defaulted: Inner { skip: true },
+               });
+}
+
+#[test]
+fn nested_deeply() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct Innermost {
+        level: Option<u8>,
+    }
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct Inner {
+        #[attire(nested)]
+        innermost: Vec<Innermost>,
+    }
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct Outer {
+        #[attire(nested)]
+        inner: Option<Inner>,
+    }
+    let input = quote! {
+        #[inner(innermost(level = 1), innermost(level = 2))]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = Outer::from(ast.attrs.as_slice());
+    assert_eq!(attrs,
+               Outer {
+                   inner: Some(Inner {
+                       innermost: vec![
+                           Innermost { level: Some(1) },
+                           Innermost { level: Some(2) },
+                       ],
+                   }),
+               });
+}
+
+#[test]
+fn nested_enum() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    enum Mode {
+        Fast,
+        Safe { level: Option<u8> },
+    }
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "outer")]
+    struct Outer {
+        #[attire(nested)]
+        mode: Option<Mode>,
+    }
+    let input = quote! {
+        #[outer(mode(safe(level = 2)))]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = Outer::from(ast.attrs.as_slice());
+    assert_eq!(attrs, Outer { mode: Some(Mode::Safe { level: Some(2) }) });
+}
+
+#[test]
+fn nested_error_path() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct Inner {
+        rename: Option<char>,
+    }
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "outer")]
+    struct Outer {
+        #[attire(nested)]
+        serde: Option<Inner>,
+    }
+    let input = quote! {
+        #[outer(serde(rename = "ab"))]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = Outer::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].to_string(),
+               "Parsing attribute value \"ab\" into a char for \
+                outer(serde(rename)) failed: expected one character");
+}
@@ -0,0 +1,167 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn camel_case() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot", rename_all = "camelCase")]
+    struct A<'a> {
+        max_len: Option<u32>,
+        #[attire(attribute = "the_name")]
+        name: Option<&'a str>,
+        #[attire(alias = "short_flag")]
+        long_flag: bool,
+    }
+    let input = quote! {
+        #[carrot(maxLen = 5, the_name = "n", short_flag)]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { max_len: Some(5), name: Some("n"), long_flag: true });
+}
+
+#[test]
+fn snake_case_unknown() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot", rename_all = "camelCase")]
+    struct A {
+        max_len: Option<u32>,
+    }
+    let input = quote! {
+        #[carrot(max_len = 5)]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    let errs = errs.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(errs, [
+        "Unknown attribute `carrot(max_len)`, did you mean `maxLen`?",
+    ]);
+}
+
+#[test]
+fn pascal_case() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot", rename_all = "PascalCase")]
+    struct A<'a> {
+        max_len: Option<u32>,
+        name: Option<&'a str>,
+    }
+    let input = quote! {
+        #[carrot(MaxLen = 5, Name = "n")]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { max_len: Some(5), name: Some("n") });
+}
+
+#[test]
+fn screaming_snake_case() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(rename_all = "SCREAMING_SNAKE_CASE")]
+    struct A {
+        max_len: Option<u32>,
+        #[attire(split_attribute_of = "limit_value")]
+        lower: Option<u32>,
+        #[attire(split_attribute_of = "limit_value")]
+        upper: Option<u32>,
+    }
+    let input = quote! {
+        #[MAX_LEN = 5]
+        #[LIMIT_VALUE = 3]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { max_len: Some(5), lower: Some(3), upper: Some(3) });
+}
+
+#[test]
+fn variants() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode", rename_all = "camelCase")]
+    enum Mode {
+        ReallyFast,
+        ReallySafe { safety_level: Option<u8> },
+    }
+    let input = quote! {
+        #[mode(reallyFast)]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    assert_eq!(Mode::from(ast.attrs.as_slice()), Mode::ReallyFast);
+    let input = quote! {
+        #[mode(reallySafe(safetyLevel = 2))]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    assert_eq!(
+        Mode::from(ast.attrs.as_slice()),
+        Mode::ReallySafe { safety_level: Some(2) });
+}
+
+#[test]
+fn kebab_case() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot", rename_all = "kebab-case")]
+    struct A<'a> {
+        name: Option<&'a str>,
+        max_len: Option<u32>,
+        #[attire(split_attribute_of = "limit_value")]
+        lower: Option<u32>,
+        #[attire(split_attribute_of = "limit_value")]
+        upper: Option<u32>,
+    }
+    let input = quote! {
+        #[carrot(name = "n", max_len = 5, limit_value = 3)]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { name: Some("n"), max_len: Some(5), lower: Some(3), upper: Some(3) });
+
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode", rename_all = "kebab-case")]
+    enum Mode {
+        ReallyFast,
+        ReallySafe { safety_level: Option<u8> },
+    }
+    let input = quote! {
+        #[mode(really_safe(safety_level = 2))]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    assert_eq!(
+        Mode::from(ast.attrs.as_slice()),
+        Mode::ReallySafe { safety_level: Some(2) });
+}
+
+#[test]
+fn kebab_case_errors() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot", rename_all = "kebab-case")]
+    struct A {
+        #[attire(required)]
+        max_len: u32,
+    }
+    let input = quote! {
+        #[carrot(max_le = 5)]
+        struct S {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    let errs = errs.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(errs, [
+        "Unknown attribute `carrot(max_le)`, did you mean `max_len`?",
+        "Missing required attribute carrot(max_len)",
+    ]);
+}
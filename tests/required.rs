@@ -0,0 +1,114 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn required_present() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A<'a> {
+        #[attire(required)]
+        a: &'a str,
+        #[attire(required)]
+        b: IpAddr,
+        #[attire(required)]
+        c: bool,
+    }
+    let input = quote! {
+        #[carrot(a = "foo", b = "127.0.0.1", c = false)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        a: "foo",
+        b: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+        c: false,
+    });
+}
+
+#[test]
+fn required_missing() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A<'a> {
+        #[attire(required)]
+        a: &'a str,
+        #[attire(required)]
+        b: IpAddr,
+    }
+    let input = quote! {
+        #[carrot(a = "foo")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    match errs[0] {
+        AFromAttributesError::Missing { ref scope, attr } => {
+            assert_eq!(scope, &["carrot"]);
+            assert_eq!(attr, "b");
+        }
+        ref err => panic!("unexpected error {:?}", err),
+    }
+    assert_eq!(errs[0].to_string(), "Missing required attribute carrot(b)");
+}
+
+#[test]
+fn required_split_missing() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A {
+        #[attire(split_attribute_of = "a", required)]
+        b: u8,
+        #[attire(split_attribute_of = "a", required)]
+        c: u8,
+    }
+    let input = quote! {
+        #[carrot(a(b = 1))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].to_string(), "Missing required attribute carrot(a(c))");
+}
+
+#[test]
+fn required_in_variant() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "mode")]
+    enum Mode {
+        Fast {
+            #[attire(required)]
+            speed: u32,
+        },
+        Safe,
+    }
+    let input = quote! {
+        #[mode(fast(speed = 5))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    assert_eq!(Mode::try_from(ast.attrs.as_slice()).unwrap(), Mode::Fast { speed: 5 });
+
+    let input = quote! {
+        #[mode(fast())]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = Mode::try_from(ast.attrs.as_slice()).unwrap_err();
+    let errs = errs.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(errs, [
+        "Missing required attribute mode(fast(speed))",
+        "Missing attribute under mode, expected one of `fast`, `safe`",
+    ]);
+}
@@ -0,0 +1,114 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn borrowed() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot", rest = "extra")]
+    struct A<'a> {
+        name: Option<&'a str>,
+        extra: Vec<&'a syn::MetaItem>,
+    }
+    let input = quote! {
+        #[carrot(name = "orange", size = 5)]
+        #[carrot(derive(Debug), skip)]
+        #[potato(colour = "brown")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    let extra = syn::parse_outer_attr("#[carrot(size = 5, derive(Debug), skip)]")
+        .unwrap();
+    let extra = match extra.value {
+        syn::MetaItem::List(_, values) => values
+            .into_iter()
+            .map(|value| match value {
+                syn::NestedMetaItem::MetaItem(item) => item,
+                syn::NestedMetaItem::Literal(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>(),
+        _ => unreachable!(),
+    };
+    assert_eq!(attrs, A {
+        name: Some("orange"),
+        extra: extra.iter().collect(),
+    });
+}
+
+#[test]
+fn owned() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot", rest = "extra")]
+    struct A {
+        size: Option<u8>,
+        extra: Vec<syn::MetaItem>,
+    }
+    let input = quote! {
+        #[carrot(size = 5, colour = "orange")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        size: Some(5),
+        extra: vec![
+            syn::MetaItem::NameValue("colour".into(), "orange".into()),
+        ],
+    });
+}
+
+#[test]
+fn nested() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(rest = "extra")]
+    struct B {
+        size: Option<u8>,
+        extra: Vec<syn::MetaItem>,
+    }
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A {
+        #[attire(nested)]
+        leaf: Option<B>,
+    }
+    let input = quote! {
+        #[carrot(leaf(size = 5, colour = "green"))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        leaf: Some(B {
+            size: Some(5),
+            extra: vec![
+                syn::MetaItem::NameValue("colour".into(), "green".into()),
+            ],
+        }),
+    });
+}
+
+#[test]
+fn unscoped() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(rest = "extra")]
+    struct A {
+        size: Option<u8>,
+        extra: Vec<syn::MetaItem>,
+    }
+    let input = quote! {
+        #[size = 5]
+        #[inline]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        size: Some(5),
+        extra: vec![syn::MetaItem::Word("inline".into())],
+    });
+}
@@ -0,0 +1,109 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+#[derive(PromAttire, Debug, PartialEq)]
+#[attire(scope = "carrot", scope = "parsnip")]
+struct A<'a> {
+    #[attire(capture = "scope")]
+    scope: Option<&'a str>,
+    colour: Option<&'a str>,
+    size: Option<u8>,
+}
+
+#[test]
+fn primary() {
+    let input = quote! {
+        #[carrot(colour = "orange")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        scope: Some("carrot"),
+        colour: Some("orange"),
+        size: None,
+    });
+}
+
+#[test]
+fn alias() {
+    let input = quote! {
+        #[parsnip(colour = "white")]
+        #[parsnip(size = 3)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        scope: Some("parsnip"),
+        colour: Some("white"),
+        size: Some(3),
+    });
+}
+
+#[test]
+fn mixed() {
+    let input = quote! {
+        #[parsnip(colour = "white")]
+        #[carrot(size = 3)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::from_derive_input(&ast).unwrap();
+    assert_eq!(attrs, A {
+        scope: Some("parsnip"),
+        colour: Some("white"),
+        size: Some(3),
+    });
+}
+
+#[test]
+fn none() {
+    let input = quote! {
+        #[potato(colour = "brown")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        scope: None,
+        colour: None,
+        size: None,
+    });
+}
+
+#[test]
+fn alias_errors() {
+    let input = quote! {
+        #[parsnip(size = "big")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errors = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("for parsnip(size)"),
+            "{}", errors[0]);
+}
+
+#[test]
+fn deprecated() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot", deprecated_scope = "parsnip")]
+    struct B<'a> {
+        #[attire(capture = "scope")]
+        scope: Option<&'a str>,
+        size: Option<u8>,
+    }
+    let input = quote! {
+        #[parsnip(size = 3)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = B::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, B { scope: Some("parsnip"), size: Some(3) });
+}
@@ -0,0 +1,91 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use std::collections::BTreeSet;
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn comma_separated() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A<'a> {
+        #[attire(separator = ",")]
+        features: Vec<&'a str>,
+    }
+    let input = quote! {
+        #[features = "a, b,c ,"]
+        #[features = "d"]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { features: vec!["a", "b", "c", "d"] });
+}
+
+#[test]
+fn whitespace_separated() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A {
+        #[attire(separator = " ")]
+        sizes: BTreeSet<u8>,
+    }
+    let input = quote! {
+        #[carrot(sizes = " 3  1\t2 ")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { sizes: [1, 2, 3].iter().cloned().collect() });
+}
+
+#[test]
+fn non_string_values() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A {
+        #[attire(separator = ",")]
+        sizes: Vec<u8>,
+    }
+    let input = quote! {
+        #[sizes = "1, 2"]
+        #[sizes = 3]
+        #[sizes(4, 5)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { sizes: vec![1, 2, 3, 4, 5] });
+}
+
+#[test]
+fn element_errors() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A {
+        #[attire(separator = ",")]
+        sizes: Vec<u8>,
+    }
+    let input = quote! {
+        #[carrot(sizes = "1, x, 3, 300")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 2);
+    match errs[0] {
+        AFromAttributesError::Parsing { ref value, attr, index, .. } => {
+            assert_eq!(value, "x");
+            assert_eq!(attr, "sizes");
+            assert_eq!(index, Some(1));
+        }
+        ref err => panic!("unexpected error {:?}", err),
+    }
+    assert_eq!(
+        errs[1].to_string(),
+        "Parsing attribute value \"300\" (element 3) into a u8 for carrot(sizes) failed: \
+         number too large to fit in target type");
+}
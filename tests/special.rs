@@ -4,6 +4,8 @@ extern crate prom_attire;
 extern crate quote;
 extern crate syn;
 
+use prom_attire::FromAttributes;
+
 #[test]
 fn special_bool_unwrapped() {
     // bool should support being an unwrapped type, defaulting to false
@@ -83,8 +85,8 @@ fn unscoped_extra_attributes_are_ignored() {
 }
 
 #[test]
-fn scoped_extra_attributes_warn() {
-    #[derive(PromAttire)]
+fn scoped_extra_attributes_error() {
+    #[derive(PromAttire, Debug)]
     #[attire(scope = "carrot")]
     struct A {}
     let input = quote! {
@@ -92,7 +94,120 @@ fn scoped_extra_attributes_warn() {
         struct C {}
     };
     let ast = syn::parse_derive_input(input.as_str()).unwrap();
-    // Should not panic here just because there's an extra attribute
-    // Not sure how warnings should be output actually.......
-    A::from(ast.attrs.as_slice());
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    match errs[0] {
+        AFromAttributesError::UnknownAttribute { ref scope, attr, suggestion } => {
+            assert_eq!(scope, &["carrot"]);
+            assert_eq!(attr, "b");
+            assert_eq!(suggestion, None);
+        }
+        ref err => panic!("unexpected error {:?}", err),
+    }
+    assert_eq!(errs[0].to_string(), "Unknown attribute `carrot(b)`");
+}
+
+#[test]
+fn scoped_extra_attributes_allowed() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot", allow_unknown)]
+    struct A {
+        a: Option<bool>,
+    }
+    let input = quote! {
+        #[carrot(a, b = "false")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    // Should not error here just because there's an extra attribute
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { a: Some(true) });
+}
+
+#[test]
+fn scoped_extra_attributes_warn() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot", allow_unknown)]
+    struct A {
+        colour: Option<bool>,
+    }
+    let input = quote! {
+        #[carrot(color, size = 2)]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let (attrs, warnings) = A::try_from_with_warnings(ast.attrs.as_slice())
+        .unwrap();
+    assert_eq!(attrs, A { colour: None });
+    let warnings = warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+    assert_eq!(warnings, [
+        "Unknown attribute `carrot(color)` ignored, did you mean `colour`?",
+        "Unknown attribute `carrot(size)` ignored",
+    ]);
+}
+
+#[test]
+fn nested_extra_attributes_error() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A {
+        #[attire(split_attribute_of = "b")]
+        c: Option<bool>,
+    }
+    let input = quote! {
+        #[carrot(b(c, d = "false"))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].to_string(), "Unknown attribute `carrot(b(d))`");
+}
+
+#[test]
+fn misspelled_attribute_suggestion() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A<'a> {
+        default: Option<&'a str>,
+        #[attire(split_attribute_of = "flavour")]
+        sweet: Option<bool>,
+    }
+    let input = quote! {
+        #[carrot(defualt = "x", falvour(swete), colour = "orange")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    let errs = errs.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(errs, [
+        "Unknown attribute `carrot(defualt)`, did you mean `default`?",
+        "Unknown attribute `carrot(falvour)`, did you mean `flavour`?",
+        "Unknown attribute `carrot(colour)`",
+    ]);
+}
+
+#[test]
+fn misspelled_nested_attribute_suggestion() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct A {
+        #[attire(split_attribute_of = "flavour")]
+        sweet: Option<bool>,
+        #[attire(split_attribute_of = "flavour")]
+        sour: Option<bool>,
+    }
+    let input = quote! {
+        #[carrot(flavour(swete))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    assert_eq!(
+        errs[0].to_string(),
+        "Unknown attribute `carrot(flavour(swete))`, did you mean `sweet`?");
 }
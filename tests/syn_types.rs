@@ -0,0 +1,107 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use prom_attire::FromAttributes;
+
+#[test]
+fn syn_types() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct A {
+        with: Option<syn::Path>,
+        ty: Option<syn::Ty>,
+        name: Option<syn::Ident>,
+        default: Option<syn::Expr>,
+        bound: Vec<syn::WherePredicate>,
+    }
+    let input = quote! {
+        #[carrot(with = "my::module", ty = "Vec<u8>", name = "foo")]
+        #[carrot(default = "vec![]", bound = "T: Clone", bound = "U: Copy")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    let bounds = syn::parse_where_clause("where T: Clone, U: Copy").unwrap();
+    assert_eq!(attrs, A {
+        with: Some(syn::parse_path("my::module").unwrap()),
+        ty: Some(syn::parse_type("Vec<u8>").unwrap()),
+        name: Some(syn::Ident::from("foo")),
+        default: Some(syn::parse_expr("vec![]").unwrap()),
+        bound: bounds.predicates,
+    });
+}
+
+#[test]
+fn syn_lit() {
+    #[derive(PromAttire, Debug, PartialEq)]
+    struct A {
+        a: Option<syn::Lit>,
+        b: Option<syn::Lit>,
+        c: Option<syn::Lit>,
+    }
+    let input = quote! {
+        #[a = "foo"]
+        #[b = 5]
+        #[c = true]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        a: Some(syn::Lit::from("foo")),
+        b: Some(syn::Lit::Int(5, syn::IntTy::Unsuffixed)),
+        c: Some(syn::Lit::Bool(true)),
+    });
+}
+
+#[test]
+fn syn_parse_error() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    struct A {
+        a: Option<syn::Path>,
+        b: Option<syn::WherePredicate>,
+    }
+    let input = quote! {
+        #[a = "not a path"]
+        #[b = "T: Clone, U: Copy"]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 2);
+    match errs[0] {
+        AFromAttributesError::Parsing { ref value, attr, .. } => {
+            assert_eq!(value, "not a path");
+            assert_eq!(attr, "a");
+        }
+        ref err => panic!("unexpected error {:?}", err),
+    }
+    assert_eq!(
+        errs[1].to_string(),
+        "Parsing attribute value \"T: Clone, U: Copy\" into a syn :: WherePredicate for b failed: \
+         expected a single where predicate but found 2");
+}
+
+#[test]
+fn syn_non_string_literal() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    struct A {
+        a: Option<syn::Ident>,
+    }
+    let input = quote! {
+        #[a = 5]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errs = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errs.len(), 1);
+    match errs[0] {
+        AFromAttributesError::LiteralTy { attr, .. } => assert_eq!(attr, "a"),
+        ref err => panic!("unexpected error {:?}", err),
+    }
+}
@@ -0,0 +1,176 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use std::collections::HashMap;
+
+use prom_attire::FromAttributes;
+
+fn short(name: &str) -> Result<(), String> {
+    if name.len() <= 6 {
+        Ok(())
+    } else {
+        Err(format!("`{}` is longer than 6 characters", name))
+    }
+}
+
+fn even(value: &u8) -> Result<(), &'static str> {
+    if value % 2 == 0 {
+        Ok(())
+    } else {
+        Err("value must be even")
+    }
+}
+
+fn sized(attrs: &A) -> Result<(), &'static str> {
+    if attrs.sizes.is_empty() && attrs.weights.is_empty() {
+        Err("either sizes or weights must be given")
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(PromAttire, Debug, PartialEq)]
+#[attire(scope = "carrot", validate = "sized")]
+struct A<'a> {
+    #[attire(validate = "short")]
+    name: Option<&'a str>,
+    #[attire(validate = "even")]
+    sizes: Vec<u8>,
+    #[attire(validate = "even")]
+    weights: HashMap<&'a str, u8>,
+}
+
+#[test]
+fn valid() {
+    let input = quote! {
+        #[carrot(name = "nantes", sizes(2, 4), weights(small = 8))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        name: Some("nantes"),
+        sizes: vec![2, 4],
+        weights: vec![("small", 8)].into_iter().collect(),
+    });
+}
+
+#[test]
+fn field() {
+    let input = quote! {
+        #[carrot(name = "chantenay", sizes(2, 3), weights(small = 5))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errors = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    let errors = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(errors, vec![
+        "Invalid attribute carrot(name): `chantenay` is longer than 6 characters",
+        "Invalid attribute carrot(sizes): value must be even",
+        "Invalid attribute carrot(weights(small)): value must be even",
+    ]);
+}
+
+#[test]
+fn strukt() {
+    let input = quote! {
+        #[carrot(name = "nantes")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errors = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Invalid attributes under carrot: either sizes or weights must be given");
+}
+
+#[test]
+fn not_run_after_errors() {
+    let input = quote! {
+        #[carrot(sizes = "big")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errors = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().starts_with("Parsing attribute value"),
+            "{}", errors[0]);
+}
+
+#[test]
+fn nested() {
+    fn sensible(mode: &Mode) -> Result<(), String> {
+        match *mode {
+            Mode::Slow { speed } if speed > 10 => {
+                Err(format!("speed {} is too fast to be slow", speed))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(validate = "sensible")]
+    enum Mode {
+        Fast,
+        Slow {
+            #[attire(default = "1")]
+            speed: u8,
+        },
+    }
+
+    #[derive(PromAttire, Debug, PartialEq)]
+    #[attire(scope = "carrot")]
+    struct B {
+        #[attire(nested)]
+        mode: Option<Mode>,
+    }
+
+    let input = quote! {
+        #[carrot(mode(slow(speed = 20)))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errors = B::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Invalid attributes under carrot(mode): speed 20 is too fast to be slow");
+
+    let input = quote! {
+        #[carrot(mode(slow(speed = 5)))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = B::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, B { mode: Some(Mode::Slow { speed: 5 }) });
+}
+
+#[test]
+fn rejected_not_seen() {
+    #[allow(dead_code)]
+    #[derive(PromAttire, Debug)]
+    #[attire(scope = "carrot")]
+    struct B<'a> {
+        #[attire(required, duplicate = "first", validate = "even")]
+        size: u8,
+        #[attire(validate = "short", requires = "bound")]
+        with: Option<&'a str>,
+        bound: Option<&'a str>,
+    }
+
+    let input = quote! {
+        #[carrot(size = 3, size = 4, with = "chantenay")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errors = B::try_from(ast.attrs.as_slice()).unwrap_err();
+    let errors = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(errors, vec![
+        "Invalid attribute carrot(size): value must be even",
+        "Invalid attribute carrot(with): `chantenay` is longer than 6 characters",
+    ]);
+}
@@ -0,0 +1,98 @@
+#[macro_use]
+extern crate prom_attire;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use std::str::FromStr;
+
+use prom_attire::FromAttributes;
+
+#[derive(AttireValue, Debug, PartialEq, Clone, Copy)]
+enum Mode {
+    Fast,
+    ReallySlow,
+    #[attire(attribute = "default", alias = "normal", alias = "std")]
+    Standard,
+}
+
+#[derive(PromAttire, Debug, PartialEq)]
+#[attire(scope = "carrot")]
+struct A {
+    mode: Option<Mode>,
+    modes: Vec<Mode>,
+}
+
+#[test]
+fn from_str() {
+    assert_eq!(Mode::from_str("fast").unwrap(), Mode::Fast);
+    assert_eq!(Mode::from_str("really-slow").unwrap(), Mode::ReallySlow);
+    assert_eq!(Mode::from_str("really_slow").unwrap(), Mode::ReallySlow);
+    assert_eq!(Mode::from_str("default").unwrap(), Mode::Standard);
+    assert_eq!(Mode::from_str("normal").unwrap(), Mode::Standard);
+    assert_eq!(Mode::from_str("std").unwrap(), Mode::Standard);
+    assert!(Mode::from_str("standard").is_err());
+}
+
+#[test]
+fn invalid() {
+    let err = Mode::from_str("slow").unwrap_err();
+    assert_eq!(err.value(), "slow");
+    assert_eq!(err.choices(), &["fast", "really-slow", "default"]);
+    assert_eq!(
+        err.to_string(),
+        "unknown value `slow`, expected one of `fast`, `really-slow`, `default`");
+}
+
+#[test]
+fn string() {
+    let input = quote! {
+        #[carrot(mode = "really-slow")]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A { mode: Some(Mode::ReallySlow), modes: vec![] });
+}
+
+#[test]
+fn word() {
+    let input = quote! {
+        #[carrot(mode(really_slow), modes(fast, "default"))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let attrs = A::try_from(ast.attrs.as_slice()).unwrap();
+    assert_eq!(attrs, A {
+        mode: Some(Mode::ReallySlow),
+        modes: vec![Mode::Fast, Mode::Standard],
+    });
+}
+
+#[test]
+fn word_several() {
+    let input = quote! {
+        #[carrot(mode(fast, normal))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errors = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Unexpected item in list attribute carrot(mode), expected a single Mode");
+}
+
+#[test]
+fn attribute_error() {
+    let input = quote! {
+        #[carrot(mode(slow))]
+        struct C {}
+    };
+    let ast = syn::parse_derive_input(input.as_str()).unwrap();
+    let errors = A::try_from(ast.attrs.as_slice()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "Parsing attribute value \"slow\" into a Mode for carrot(mode) failed: unknown value `slow`, expected one of `fast`, `really-slow`, `default`");
+}